@@ -47,3 +47,21 @@ fn read_u64<T: Endianness>(b: &mut Bencher) {
         }
     })
 }
+
+#[bench]
+fn read_u64_into_be(b: &mut Bencher) {
+    read_u64_into::<BigEndian>(b);
+}
+
+#[bench]
+fn read_u64_into_le(b: &mut Bencher) {
+    read_u64_into::<LittleEndian>(b);
+}
+
+fn read_u64_into<T: Endianness>(b: &mut Bencher) {
+    let mut dst = vec![0u64; BENCH_SIZE];
+    b.iter(|| {
+        let mut reader : &[u8] = &[0; BENCH_SIZE * 8];
+        reader.read_u64_into::<T>(&mut dst).unwrap();
+    })
+}
@@ -0,0 +1,516 @@
+//! Derive macro for the `ReadPod` and `WritePod` traits of podio
+//!
+//! Use it through the `derive` feature of podio, which re-exports it as `podio::Pod`.
+//!
+//! Fields are read and written in declaration order. By default they use the endianness the
+//! caller passes to `read_pod`/`write_pod`, which can be fixed per struct or per field with
+//! `#[podio(endian = "...")]`, taking `"le"`, `"be"`, `"native"` or `"network"`.
+//!
+//! Constant and reserved bytes are handled with further attributes:
+//!
+//! - `#[podio(magic = b"...")]` on the struct or on a field expects these bytes at the start of
+//!   the struct or right before the field. A mismatch fails with a `podio::MagicMismatch`.
+//! - `#[podio(pad = N)]` on a field skips `N` reserved bytes right before it, and writes them as
+//!   zeros. On the struct, the padding comes after the last field.
+//! - `#[podio(skip)]` on a field leaves it out of the encoding, reading it as `Default::default()`.
+//!
+//! A `Vec<T>` field needs to know its number of elements, given by one of:
+//!
+//! - `#[podio(count = "field")]`, naming an earlier integer field holding the count. When
+//!   writing, that field must match the length of the `Vec`, or `io::ErrorKind::InvalidInput`
+//!   is returned before anything is written.
+//! - `#[podio(prefix = u16)]`, storing the count right before the elements, with any type
+//!   implementing `podio::LengthPrefix`.
+//!
+//! Adding `#[podio(max = N)]` rejects larger counts with a `podio::LimitExceeded` when reading.
+//!
+//! Fieldless enums with a `#[repr(u8)]`, `u16`, `u32` or `u64` are read and written as their
+//! discriminant. An unknown discriminant fails with a `podio::UnknownDiscriminant`, unless one
+//! variant is marked `#[podio(other)]` and holds a single field of the repr type, in which case
+//! it keeps the unrecognized value. Writing that variant with the discriminant of another
+//! variant fails with `io::ErrorKind::InvalidInput`, as it would read back as the other variant.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro2::TokenStream;
+use syn::{Attribute, Data, DataEnum, DataStruct, DeriveInput, Fields, Ident, LitByteStr, LitInt, LitStr, Type};
+
+/// Derive `ReadPod` and `WritePod` for a struct or a fieldless enum
+#[proc_macro_derive(Pod, attributes(podio))]
+pub fn derive_pod(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Options shared by containers and fields
+#[derive(Default)]
+struct Attrs {
+    endian: Option<TokenStream>,
+    other: bool,
+    magic: Option<LitByteStr>,
+    pad: Option<usize>,
+    skip: bool,
+    count: Option<Ident>,
+    prefix: Option<Type>,
+    max: Option<usize>,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Attrs> {
+        let mut res = Attrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("podio")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("endian") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    res.endian = Some(parse_endian(&lit)?);
+                    Ok(())
+                } else if meta.path.is_ident("other") {
+                    res.other = true;
+                    Ok(())
+                } else if meta.path.is_ident("magic") {
+                    res.magic = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("pad") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    res.pad = Some(lit.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    res.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("count") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    res.count = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("prefix") {
+                    res.prefix = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("max") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    res.max = Some(lit.base10_parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown podio attribute"))
+                }
+            })?;
+        }
+        Ok(res)
+    }
+}
+
+fn parse_endian(lit: &LitStr) -> syn::Result<TokenStream> {
+    match &*lit.value() {
+        "le" | "little" => Ok(quote!(::podio::LittleEndian)),
+        "be" | "big" => Ok(quote!(::podio::BigEndian)),
+        "native" => Ok(quote!(::podio::NativeEndian)),
+        "network" => Ok(quote!(::podio::NetworkEndian)),
+        _ => Err(syn::Error::new(lit.span(), "expected \"le\", \"be\", \"native\" or \"network\"")),
+    }
+}
+
+struct Field<'a> {
+    /// Name of the field, or its index for tuple structs
+    member: syn::Member,
+    /// Local variable the field is read into
+    binding: syn::Ident,
+    ty: &'a Type,
+    endian: TokenStream,
+    /// Bytes expected right before the field
+    magic: Option<LitByteStr>,
+    /// Number of reserved bytes right before the field
+    pad: Option<usize>,
+    /// Not encoded, filled with `Default::default()` when reading
+    skip: bool,
+    /// Set for `Vec` fields
+    vec: Option<VecField<'a>>,
+}
+
+/// Where the number of elements of a `Vec` field is stored
+enum VecLen {
+    /// In an earlier field
+    Count(Ident),
+    /// Right before the elements
+    Prefix(Type),
+}
+
+struct VecField<'a> {
+    elem: &'a Type,
+    len: VecLen,
+    max: Option<usize>,
+}
+
+/// Returns `T` for a field of type `Vec<T>`
+fn vec_elem(ty: &Type) -> Option<&Type> {
+    let path = match *ty {
+        Type::Path(ref path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Vec" {
+        return None;
+    }
+    match last.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+            syn::GenericArgument::Type(ref elem) => Some(elem),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn read_magic(magic: &LitByteStr) -> TokenStream {
+    let len = magic.value().len();
+    quote! {
+        let mut __found = [0u8; #len];
+        ::podio::__private::read_bytes(__reader, &mut __found)?;
+        if __found != *#magic {
+            return ::podio::__private::Err(::podio::Error::from(::podio::MagicMismatch::new(#magic, &__found)));
+        }
+    }
+}
+
+fn write_magic(magic: &LitByteStr) -> TokenStream {
+    quote! {
+        ::podio::PodWrite::pod_write_all(__writer, #magic)?;
+    }
+}
+
+/// Initial capacity limit for `Vec` fields, so a bogus count does not exhaust memory up front
+const VEC_PREALLOC: u64 = 1024;
+
+fn read_vec(binding: &Ident, vec: &VecField, endian: &TokenStream) -> TokenStream {
+    let elem = vec.elem;
+    let count = match vec.len {
+        VecLen::Count(ref count) => quote! {
+            <u64 as ::podio::__private::TryFrom<_>>::try_from(#count).map_err(|_| {
+                ::podio::Error::new(::podio::ErrorKind::InvalidData, "Negative element count")
+            })?
+        },
+        VecLen::Prefix(ref prefix) => quote! {
+            <#prefix as ::podio::LengthPrefix>::read_len::<__R, #endian>(__reader)?
+        },
+    };
+    let check = vec.max.map(|max| quote! {
+        if __count > #max as u64 {
+            return ::podio::__private::Err(::podio::Error::from(::podio::LimitExceeded::new(__count, #max)));
+        }
+    });
+    quote! {
+        let __count: u64 = #count;
+        #check
+        let mut #binding = ::podio::__private::Vec::with_capacity(::podio::__private::min(__count, #VEC_PREALLOC) as usize);
+        for _ in 0..__count {
+            #binding.push(<#elem as ::podio::ReadPod>::read_from::<__R, #endian>(__reader)?);
+        }
+    }
+}
+
+fn write_vec(member: &syn::Member, vec: &VecField, endian: &TokenStream) -> TokenStream {
+    let elem = vec.elem;
+    let prefix = match vec.len {
+        VecLen::Count(_) => None,
+        VecLen::Prefix(ref prefix) => Some(quote! {
+            <#prefix as ::podio::LengthPrefix>::write_len::<__W, #endian>(__writer, self.#member.len())?;
+        }),
+    };
+    quote! {
+        #prefix
+        for __elem in &self.#member {
+            <#elem as ::podio::WritePod>::write_to::<__W, #endian>(__elem, __writer)?;
+        }
+    }
+}
+
+fn read_pad(pad: usize) -> TokenStream {
+    quote! {
+        ::podio::__private::skip_pad(__reader, #pad)?;
+    }
+}
+
+fn write_pad(pad: usize) -> TokenStream {
+    quote! {
+        ::podio::__private::write_pad(__writer, #pad)?;
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let container = Attrs::parse(&input.attrs)?;
+    if container.other || container.skip || container.count.is_some() || container.prefix.is_some() || container.max.is_some() {
+        return Err(syn::Error::new_spanned(input, "only #[podio(endian)], #[podio(magic)] and #[podio(pad)] are allowed on the type itself"));
+    }
+
+    match input.data {
+        Data::Struct(ref data) => expand_struct(input, data, container),
+        Data::Enum(ref data) => expand_enum(input, data, container),
+        Data::Union(_) => Err(syn::Error::new_spanned(input, "Pod cannot be derived for unions")),
+    }
+}
+
+fn expand_struct(input: &DeriveInput, data: &DataStruct, container: Attrs) -> syn::Result<TokenStream> {
+    let default_endian = container.endian.unwrap_or_else(|| quote!(__E));
+    let mut fields = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let attrs = Attrs::parse(&field.attrs)?;
+        if attrs.other {
+            return Err(syn::Error::new_spanned(field, "#[podio(other)] is only allowed on enum variants"));
+        }
+        if attrs.skip && attrs.endian.is_some() {
+            return Err(syn::Error::new_spanned(field, "a skipped field has no endianness"));
+        }
+        let (member, binding) = match field.ident {
+            Some(ref ident) => (syn::Member::Named(ident.clone()), ident.clone()),
+            None => (syn::Member::Unnamed(i.into()), format_ident!("__field{}", i)),
+        };
+        let len = match (attrs.count, attrs.prefix) {
+            (Some(count), None) => {
+                if !fields.iter().any(|f: &Field| f.binding == count && !f.skip && f.vec.is_none()) {
+                    return Err(syn::Error::new_spanned(&count, "count must name an earlier, encoded integer field"));
+                }
+                Some(VecLen::Count(count))
+            }
+            (None, Some(prefix)) => Some(VecLen::Prefix(prefix)),
+            (None, None) => None,
+            (Some(_), Some(_)) => return Err(syn::Error::new_spanned(field, "count and prefix cannot be combined")),
+        };
+        let vec = match len {
+            Some(len) => match vec_elem(&field.ty) {
+                Some(elem) if !attrs.skip => Some(VecField { elem, len, max: attrs.max }),
+                _ => return Err(syn::Error::new_spanned(field, "count and prefix are only allowed on encoded Vec fields")),
+            },
+            None if attrs.max.is_some() => {
+                return Err(syn::Error::new_spanned(field, "max needs a count or prefix"));
+            }
+            None => None,
+        };
+        fields.push(Field {
+            member,
+            binding,
+            ty: &field.ty,
+            endian: attrs.endian.unwrap_or_else(|| default_endian.clone()),
+            magic: attrs.magic,
+            pad: attrs.pad,
+            skip: attrs.skip,
+            vec,
+        });
+    }
+
+    let name = &input.ident;
+
+    let mut read_generics = input.generics.clone();
+    let mut write_generics = input.generics.clone();
+    for field in &fields {
+        let ty = field.ty;
+        if field.skip {
+            read_generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::podio::__private::Default));
+        } else if let Some(ref vec) = field.vec {
+            let elem = vec.elem;
+            read_generics.make_where_clause().predicates.push(syn::parse_quote!(#elem: ::podio::ReadPod));
+            write_generics.make_where_clause().predicates.push(syn::parse_quote!(#elem: ::podio::WritePod));
+        } else {
+            read_generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::podio::ReadPod));
+            write_generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::podio::WritePod));
+        }
+    }
+    let (read_impl, ty_generics, read_where) = read_generics.split_for_impl();
+    let (write_impl, _, write_where) = write_generics.split_for_impl();
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    // Count fields are checked before anything is written
+    for f in &fields {
+        if let Some(VecField { len: VecLen::Count(ref count), .. }) = f.vec {
+            let member = &f.member;
+            let message = format!("{} does not match the length of {}", count, f.binding);
+            writes.push(quote! {
+                if <u64 as ::podio::__private::TryFrom<_>>::try_from(self.#count).ok() != ::podio::__private::Some(self.#member.len() as u64) {
+                    return ::podio::__private::Err(::podio::Error::new(::podio::ErrorKind::InvalidInput, #message));
+                }
+            });
+        }
+    }
+    if let Some(ref magic) = container.magic {
+        reads.push(read_magic(magic));
+        writes.push(write_magic(magic));
+    }
+    for f in &fields {
+        let (member, binding, ty, endian) = (&f.member, &f.binding, f.ty, &f.endian);
+        if let Some(ref magic) = f.magic {
+            reads.push(read_magic(magic));
+            writes.push(write_magic(magic));
+        }
+        if let Some(pad) = f.pad {
+            reads.push(read_pad(pad));
+            writes.push(write_pad(pad));
+        }
+        if f.skip {
+            reads.push(quote!(let #binding = ::podio::__private::Default::default();));
+        } else if let Some(ref vec) = f.vec {
+            reads.push(read_vec(binding, vec, endian));
+            writes.push(write_vec(member, vec, endian));
+        } else {
+            reads.push(quote! {
+                let #binding = <#ty as ::podio::ReadPod>::read_from::<__R, #endian>(__reader)?;
+            });
+            writes.push(quote! {
+                <#ty as ::podio::WritePod>::write_to::<__W, #endian>(&self.#member, __writer)?;
+            });
+        }
+    }
+    if let Some(pad) = container.pad {
+        reads.push(read_pad(pad));
+        writes.push(write_pad(pad));
+    }
+    let bindings = fields.iter().map(|f| &f.binding);
+    let construct = match data.fields {
+        Fields::Named(_) => quote!(#name { #(#bindings),* }),
+        Fields::Unnamed(_) => quote!(#name ( #(#bindings),* )),
+        Fields::Unit => quote!(#name),
+    };
+
+    Ok(quote! {
+        impl #read_impl ::podio::ReadPod for #name #ty_generics #read_where {
+            #[allow(unused_variables)]
+            fn read_from<__R: ::podio::PodRead, __E: ::podio::Endianness>(__reader: &mut __R) -> ::podio::Result<Self> {
+                #(#reads)*
+                ::podio::__private::Ok(#construct)
+            }
+        }
+
+        impl #write_impl ::podio::WritePod for #name #ty_generics #write_where {
+            #[allow(unused_variables)]
+            fn write_to<__W: ::podio::PodWrite, __E: ::podio::Endianness>(&self, __writer: &mut __W) -> ::podio::Result<()> {
+                #(#writes)*
+                ::podio::__private::Ok(())
+            }
+        }
+    })
+}
+
+/// Finds the integer type in `#[repr(...)]`
+fn parse_repr(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    let mut repr = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            for ty in &["u8", "u16", "u32", "u64"] {
+                if meta.path.is_ident(ty) {
+                    repr = Some(Ident::new(ty, meta.path.get_ident().unwrap().span()));
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr)
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum, container: Attrs) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let endian = container.endian.unwrap_or_else(|| quote!(__E));
+    if container.magic.is_some() || container.pad.is_some() {
+        return Err(syn::Error::new_spanned(input, "#[podio(magic)] and #[podio(pad)] are not supported on enums"));
+    }
+    let repr = match parse_repr(&input.attrs)? {
+        Some(repr) => repr,
+        None => return Err(syn::Error::new_spanned(input, "Pod enums need a #[repr(u8)], #[repr(u16)], #[repr(u32)] or #[repr(u64)]")),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "Pod cannot be derived for generic enums"));
+    }
+
+    let mut consts = Vec::new();
+    let mut known = Vec::new();
+    let mut read_arms = Vec::new();
+    let mut write_arms = Vec::new();
+    let mut other = None;
+    let mut next = quote!(0);
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let attrs = Attrs::parse(&variant.attrs)?;
+        if attrs.endian.is_some() || attrs.magic.is_some() || attrs.pad.is_some() || attrs.skip
+            || attrs.count.is_some() || attrs.prefix.is_some() || attrs.max.is_some() {
+            return Err(syn::Error::new_spanned(variant, "only #[podio(other)] is allowed on enum variants"));
+        }
+        if attrs.other {
+            if other.is_some() {
+                return Err(syn::Error::new_spanned(variant, "only one variant can be #[podio(other)]"));
+            }
+            match variant.fields {
+                Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {}
+                _ => return Err(syn::Error::new_spanned(variant, "#[podio(other)] needs a single unnamed field of the repr type")),
+            }
+            let disc = match variant.discriminant {
+                Some((_, ref expr)) => quote!(#expr),
+                None => next,
+            };
+            next = quote!((#disc) + 1);
+            other = Some(ident);
+            continue;
+        }
+        if !variant.fields.is_empty() {
+            return Err(syn::Error::new_spanned(variant, "Pod enums can only have fieldless variants"));
+        }
+
+        let disc = match variant.discriminant {
+            Some((_, ref expr)) => quote!(#expr),
+            None => next,
+        };
+        let konst = format_ident!("__PODIO_{}", ident);
+        consts.push(quote!(const #konst: #repr = #disc;));
+        known.push(quote!(#konst));
+        read_arms.push(quote!(__raw if __raw == #konst => ::podio::__private::Ok(#name::#ident),));
+        write_arms.push(quote!(#name::#ident => #konst,));
+        next = quote!(#konst + 1);
+    }
+
+    let fallback = match other {
+        Some(ident) => quote!(__raw => ::podio::__private::Ok(#name::#ident(__raw)),),
+        None => {
+            let type_name = name.to_string();
+            quote! {
+                __raw => ::podio::__private::Err(::podio::Error::from(::podio::UnknownDiscriminant::new(#type_name, __raw as u64))),
+            }
+        }
+    };
+    if let Some(ident) = other {
+        // A known discriminant would read back as its own variant
+        let check = if known.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                if #(__raw == #known)||* {
+                    return ::podio::__private::Err(::podio::Error::new(::podio::ErrorKind::InvalidInput, "Value of the #[podio(other)] variant is a known discriminant"));
+                }
+            })
+        };
+        write_arms.push(quote! {
+            #name::#ident(__raw) => {
+                #check
+                __raw
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::podio::ReadPod for #name {
+            fn read_from<__R: ::podio::PodRead, __E: ::podio::Endianness>(__reader: &mut __R) -> ::podio::Result<Self> {
+                #(#[allow(non_upper_case_globals)] #consts)*
+                match <#repr as ::podio::ReadPod>::read_from::<__R, #endian>(__reader)? {
+                    #(#read_arms)*
+                    #fallback
+                }
+            }
+        }
+
+        impl ::podio::WritePod for #name {
+            fn write_to<__W: ::podio::PodWrite, __E: ::podio::Endianness>(&self, __writer: &mut __W) -> ::podio::Result<()> {
+                #(#[allow(non_upper_case_globals)] #consts)*
+                let __raw: #repr = match *self {
+                    #(#write_arms)*
+                };
+                <#repr as ::podio::WritePod>::write_to::<__W, #endian>(&__raw, __writer)
+            }
+        }
+    })
+}
@@ -0,0 +1,288 @@
+extern crate podio;
+
+use std::io;
+use podio::{LittleEndian, BigEndian};
+use podio::{ReadPodExt, WritePodExt, Pod};
+
+#[derive(Pod, Debug, PartialEq)]
+struct Plain {
+    a: u8,
+    b: u16,
+    c: [f32; 2],
+}
+
+#[derive(Pod, Debug, PartialEq)]
+#[podio(endian = "be")]
+struct Fixed {
+    magic: u32,
+    #[podio(endian = "le")]
+    len: u16,
+    inner: (i8, u16),
+}
+
+#[derive(Pod, Debug, PartialEq)]
+struct Tuple(u16, #[podio(endian = "network")] u16);
+
+#[derive(Pod, Debug, PartialEq)]
+struct Unit;
+
+#[derive(Pod, Debug, PartialEq)]
+struct Nested {
+    plain: Plain,
+    tuple: Tuple,
+}
+
+#[derive(Pod, Debug, PartialEq)]
+struct Generic<T> {
+    val: T,
+}
+
+fn roundtrip<T: podio::ReadPod + podio::WritePod>(val: &T, bytes: &[u8]) -> T {
+    let mut writer = io::Cursor::new(Vec::new());
+    writer.write_pod::<LittleEndian>(val).unwrap();
+    assert_eq!(writer.get_ref(), bytes);
+
+    let mut reader = io::Cursor::new(writer.into_inner());
+    let res = reader.read_pod::<T, LittleEndian>().unwrap();
+    assert_eq!(reader.position(), bytes.len() as u64);
+    res
+}
+
+#[test]
+fn plain() {
+    let val = Plain { a: 1, b: 0x0203, c: [1.0, -2.0] };
+    let bytes = [0x01, 0x03, 0x02, 0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0];
+    assert_eq!(roundtrip(&val, &bytes), val);
+
+    let mut reader: &[u8] = &[0x01, 0x02, 0x03, 0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0];
+    let val = reader.read_pod::<Plain, BigEndian>().unwrap();
+    assert_eq!(val.b, 0x0203);
+    assert_eq!(val.c, [f32::from_bits(0x0000803f), f32::from_bits(0x000000c0)]);
+}
+
+#[test]
+fn fixed_endian() {
+    let val = Fixed { magic: 0x7f454c46, len: 0x0102, inner: (-1, 0x0304) };
+    let bytes = [0x7f, 0x45, 0x4c, 0x46, 0x02, 0x01, 0xff, 0x03, 0x04];
+    assert_eq!(roundtrip(&val, &bytes), val);
+
+    // The caller's endianness does not matter
+    let mut reader: &[u8] = &bytes;
+    assert_eq!(reader.read_pod::<Fixed, BigEndian>().unwrap(), val);
+}
+
+#[test]
+fn tuple_and_unit() {
+    let val = Tuple(0x0102, 0x0304);
+    assert_eq!(roundtrip(&val, &[0x02, 0x01, 0x03, 0x04]), val);
+    assert_eq!(roundtrip(&Unit, &[]), Unit);
+}
+
+#[test]
+fn nested_and_generic() {
+    let val = Nested { plain: Plain { a: 1, b: 2, c: [0.0, 0.0] }, tuple: Tuple(3, 4) };
+    let bytes = [0x01, 0x02, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0x03, 0x00, 0x00, 0x04];
+    assert_eq!(roundtrip(&val, &bytes), val);
+
+    let val = Generic { val: 0x01020304u32 };
+    assert_eq!(roundtrip(&val, &[0x04, 0x03, 0x02, 0x01]), val);
+}
+
+#[test]
+fn truncated() {
+    let mut reader: &[u8] = &[0x7f, 0x45, 0x4c, 0x46, 0x02];
+    assert_eq!(reader.read_pod::<Fixed, BigEndian>().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[derive(Pod, Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum Class {
+    None,
+    Elf32,
+    Elf64,
+}
+
+#[derive(Pod, Debug, PartialEq)]
+#[repr(u16)]
+#[podio(endian = "be")]
+enum Machine {
+    X86 = 0x03,
+    Arm = 0x28,
+    Amd64 = 0x3e,
+    Next,
+}
+
+#[derive(Pod, Debug, PartialEq)]
+#[repr(u32)]
+enum Kind {
+    A = 1,
+    B = 2,
+    #[podio(other)]
+    Unknown(u32),
+}
+
+#[test]
+fn enums() {
+    assert_eq!(roundtrip(&Class::Elf64, &[0x02]), Class::Elf64);
+    assert_eq!(roundtrip(&[Class::None, Class::Elf32], &[0x00, 0x01]), [Class::None, Class::Elf32]);
+    assert_eq!(roundtrip(&Machine::Amd64, &[0x00, 0x3e]), Machine::Amd64);
+    assert_eq!(roundtrip(&Machine::Next, &[0x00, 0x3f]), Machine::Next);
+    assert_eq!(roundtrip(&Kind::B, &[0x02, 0x00, 0x00, 0x00]), Kind::B);
+    assert_eq!(roundtrip(&Kind::Unknown(7), &[0x07, 0x00, 0x00, 0x00]), Kind::Unknown(7));
+}
+
+#[test]
+fn unknown_discriminant() {
+    let mut reader: &[u8] = &[0x00, 0x29];
+    let err = reader.read_pod::<Machine, LittleEndian>().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let unknown = err.get_ref().and_then(|e| e.downcast_ref::<podio::UnknownDiscriminant>()).unwrap();
+    assert_eq!(unknown.type_name(), "Machine");
+    assert_eq!(unknown.value(), 0x29);
+
+    let mut reader: &[u8] = &[0x03];
+    assert_eq!(reader.read_pod::<Class, LittleEndian>().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut reader: &[u8] = &[0x05, 0x00, 0x00, 0x00];
+    assert_eq!(reader.read_pod::<Kind, LittleEndian>().unwrap(), Kind::Unknown(5));
+}
+
+#[test]
+fn other_known_discriminant() {
+    let mut out = Vec::new();
+    let err = out.write_pod::<LittleEndian>(&Kind::Unknown(1)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+
+    out.write_pod::<LittleEndian>(&Kind::Unknown(3)).unwrap();
+    assert_eq!(out, [0x03, 0x00, 0x00, 0x00]);
+}
+
+#[derive(Pod, Debug, PartialEq, Default)]
+#[podio(magic = b"\x7fELF", endian = "le")]
+struct Ident {
+    class: u8,
+    #[podio(pad = 3)]
+    version: u16,
+    #[podio(skip)]
+    parsed: bool,
+    #[podio(magic = b"PK")]
+    tail: u8,
+}
+
+#[derive(Pod, Debug, PartialEq)]
+#[podio(pad = 2)]
+struct Trailing(u8);
+
+#[test]
+fn magic_and_padding() {
+    let val = Ident { class: 2, version: 1, parsed: false, tail: 9 };
+    let bytes = [0x7f, b'E', b'L', b'F', 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, b'P', b'K', 0x09];
+    assert_eq!(roundtrip(&val, &bytes), val);
+
+    // Skipped fields are not written, and padding is written as zeros
+    let val = Ident { class: 2, version: 1, parsed: true, tail: 9 };
+    let mut out = Vec::new();
+    out.write_pod::<BigEndian>(&val).unwrap();
+    assert_eq!(out, bytes);
+
+    // Reserved bytes are ignored when reading
+    let mut reader: &[u8] = &[0x7f, b'E', b'L', b'F', 0x02, 0xaa, 0xbb, 0xcc, 0x01, 0x00, b'P', b'K', 0x09];
+    assert_eq!(reader.read_pod::<Ident, BigEndian>().unwrap(), Ident { class: 2, version: 1, parsed: false, tail: 9 });
+
+    assert_eq!(roundtrip(&Trailing(1), &[0x01, 0x00, 0x00]), Trailing(1));
+}
+
+#[derive(Pod, Debug, PartialEq)]
+struct Reserved {
+    head: u8,
+    #[podio(pad = 4194304)]
+    tail: u8,
+}
+
+#[test]
+fn large_padding() {
+    // Padding is streamed through a small buffer, so it fits on a small stack
+    let handle = std::thread::Builder::new().stack_size(64 * 1024).spawn(|| {
+        let mut bytes = vec![0u8; 4194306];
+        bytes[0] = 1;
+        bytes[4194305] = 2;
+        let mut reader: &[u8] = &bytes;
+        let val = reader.read_pod::<Reserved, BigEndian>().unwrap();
+        assert_eq!(val, Reserved { head: 1, tail: 2 });
+
+        let mut out = Vec::new();
+        out.write_pod::<BigEndian>(&val).unwrap();
+        assert_eq!(out, bytes);
+
+        let mut reader: &[u8] = &bytes[..1000];
+        let err = reader.read_pod::<Reserved, BigEndian>().unwrap_err();
+        let eof = err.get_ref().and_then(|e| e.downcast_ref::<podio::UnexpectedEof>()).unwrap();
+        assert_eq!(eof.bytes_read(), 999);
+        assert_eq!(eof.bytes_expected(), 4194304);
+    }).unwrap();
+    handle.join().unwrap();
+}
+
+#[test]
+fn magic_mismatch() {
+    let mut reader: &[u8] = &[0x7f, b'E', b'L', b'X', 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, b'P', b'K', 0x09];
+    let err = reader.read_pod::<Ident, BigEndian>().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mismatch = err.get_ref().and_then(|e| e.downcast_ref::<podio::MagicMismatch>()).unwrap();
+    assert_eq!(mismatch.expected(), b"\x7fELF");
+    assert_eq!(mismatch.found(), b"\x7fELX");
+
+    let mut reader: &[u8] = &[0x7f, b'E', b'L', b'F', 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, b'P', b'X', 0x09];
+    assert_eq!(reader.read_pod::<Ident, BigEndian>().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[derive(Pod, Debug, PartialEq)]
+#[podio(endian = "be")]
+struct Table {
+    num_entries: u16,
+    flags: u8,
+    #[podio(count = "num_entries", max = 4)]
+    entries: Vec<(u8, u16)>,
+    #[podio(prefix = u8)]
+    names: Vec<u8>,
+    #[podio(prefix = podio::Uleb128, endian = "le")]
+    wide: Vec<u16>,
+}
+
+#[test]
+fn vec_fields() {
+    let val = Table { num_entries: 2, flags: 7, entries: vec![(1, 2), (3, 4)], names: vec![b'a', b'b'], wide: vec![0x0102] };
+    let bytes = [0x00, 0x02, 0x07, 0x01, 0x00, 0x02, 0x03, 0x00, 0x04, 0x02, b'a', b'b', 0x01, 0x02, 0x01];
+    assert_eq!(roundtrip(&val, &bytes), val);
+
+    let val = Table { num_entries: 0, flags: 0, entries: vec![], names: vec![], wide: vec![] };
+    assert_eq!(roundtrip(&val, &[0x00, 0x00, 0x00, 0x00, 0x00]), val);
+}
+
+#[test]
+fn vec_count_mismatch() {
+    let val = Table { num_entries: 3, flags: 7, entries: vec![(1, 2)], names: vec![], wide: vec![] };
+    let mut out = Vec::new();
+    assert_eq!(out.write_pod::<BigEndian>(&val).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+
+    let val = Table { num_entries: 0, flags: 0, entries: vec![], names: vec![0; 256], wide: vec![] };
+    assert_eq!(out.write_pod::<BigEndian>(&val).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn vec_limits() {
+    let mut reader: &[u8] = &[0x00, 0x05, 0x07];
+    let err = reader.read_pod::<Table, BigEndian>().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let limit = err.get_ref().and_then(|e| e.downcast_ref::<podio::LimitExceeded>()).unwrap();
+    assert_eq!(limit.requested(), 5);
+    assert_eq!(limit.limit(), 4);
+
+    // A bogus prefix runs into the end of the stream instead of a huge allocation
+    let mut reader: &[u8] = &[0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0f];
+    assert_eq!(reader.read_pod::<Table, BigEndian>().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
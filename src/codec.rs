@@ -0,0 +1,45 @@
+//! `const` conversions between values and their byte representation
+//!
+//! These are the single source of truth for the fixed-width encodings: `ReadPodExt`,
+//! `WritePodExt` and `SliceReader` delegate to them.
+
+macro_rules! codec {
+    ($($ty:ident, $n:expr, $enc_le:ident, $enc_be:ident, $dec_le:ident, $dec_be:ident;)*) => {$(
+        #[doc = concat!("Encode a ", stringify!($ty), " as little endian bytes")]
+        #[inline]
+        pub const fn $enc_le(val: $ty) -> [u8; $n] {
+            val.to_le_bytes()
+        }
+
+        #[doc = concat!("Encode a ", stringify!($ty), " as big endian bytes")]
+        #[inline]
+        pub const fn $enc_be(val: $ty) -> [u8; $n] {
+            val.to_be_bytes()
+        }
+
+        #[doc = concat!("Decode a ", stringify!($ty), " from little endian bytes")]
+        #[inline]
+        pub const fn $dec_le(buf: [u8; $n]) -> $ty {
+            $ty::from_le_bytes(buf)
+        }
+
+        #[doc = concat!("Decode a ", stringify!($ty), " from big endian bytes")]
+        #[inline]
+        pub const fn $dec_be(buf: [u8; $n]) -> $ty {
+            $ty::from_be_bytes(buf)
+        }
+    )*};
+}
+
+codec! {
+    u16, 2, encode_u16_le, encode_u16_be, decode_u16_le, decode_u16_be;
+    u32, 4, encode_u32_le, encode_u32_be, decode_u32_le, decode_u32_be;
+    u64, 8, encode_u64_le, encode_u64_be, decode_u64_le, decode_u64_be;
+    u128, 16, encode_u128_le, encode_u128_be, decode_u128_le, decode_u128_be;
+    i16, 2, encode_i16_le, encode_i16_be, decode_i16_le, decode_i16_be;
+    i32, 4, encode_i32_le, encode_i32_be, decode_i32_le, decode_i32_be;
+    i64, 8, encode_i64_le, encode_i64_be, decode_i64_le, decode_i64_be;
+    i128, 16, encode_i128_le, encode_i128_be, decode_i128_le, decode_i128_be;
+    f32, 4, encode_f32_le, encode_f32_be, decode_f32_le, decode_f32_be;
+    f64, 8, encode_f64_le, encode_f64_be, decode_f64_le, decode_f64_be;
+}
@@ -0,0 +1,217 @@
+//! Minimal byte source and sink traits, backed by `std::io` when the `std` feature is enabled
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Result};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{Error, ErrorKind, Result};
+
+use {CapacityExceeded, LimitExceeded, MagicMismatch, UnexpectedEof, UnknownDiscriminant};
+
+/// A source of bytes, the core of `ReadPodExt`
+///
+/// With the `std` feature, this is implemented for every `std::io::Read`. Without it, it is
+/// implemented for `&[u8]`.
+pub trait PodRead {
+    /// Read some bytes into `buf`, returning how many were read
+    ///
+    /// Returning 0 for a non-empty `buf` signals the end of the stream.
+    fn pod_read(&mut self, buf: &mut [u8]) -> Result<usize>;
+}
+
+/// A sink for bytes, the core of `WritePodExt`
+///
+/// With the `std` feature, this is implemented for every `std::io::Write`. Without it, it is
+/// implemented for `Vec<u8>` and `&mut [u8]`.
+pub trait PodWrite {
+    /// Write all of `buf`
+    fn pod_write_all(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read + ?Sized> PodRead for R {
+    fn pod_read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            match self.read(buf) {
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> PodWrite for W {
+    fn pod_write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.write_all(buf)
+    }
+}
+
+macro_rules! impl_from_payload {
+    ($ty:ident, $kind:ident) => {
+        #[cfg(feature = "std")]
+        impl From<$ty> for Error {
+            fn from(payload: $ty) -> Error {
+                Error::new(ErrorKind::$kind, payload)
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl From<$ty> for Error {
+            fn from(payload: $ty) -> Error {
+                Error::with_payload(ErrorKind::$kind, no_std::Payload::$ty(payload))
+            }
+        }
+    };
+}
+
+impl_from_payload!(UnexpectedEof, UnexpectedEof);
+impl_from_payload!(LimitExceeded, InvalidData);
+impl_from_payload!(UnknownDiscriminant, InvalidData);
+impl_from_payload!(MagicMismatch, InvalidData);
+impl_from_payload!(CapacityExceeded, WriteZero);
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::vec::Vec;
+    use core::any::Any;
+    use core::cmp;
+    use core::fmt;
+    use core::result;
+
+    use {CapacityExceeded, LimitExceeded, MagicMismatch, UnexpectedEof, UnknownDiscriminant};
+    use super::{PodRead, PodWrite};
+
+    /// The result of a podio operation
+    pub type Result<T> = result::Result<T, Error>;
+
+    /// The kind of an `Error`, a subset of `std::io::ErrorKind`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// The data read was not valid for the requested value
+        InvalidData,
+        /// The value to write cannot be encoded
+        InvalidInput,
+        /// The end of the stream was reached before the value was complete
+        UnexpectedEof,
+        /// The sink is full
+        WriteZero,
+        /// Any other error
+        Other,
+    }
+
+    #[derive(Debug)]
+    pub enum Payload {
+        Message(&'static str),
+        UnexpectedEof(UnexpectedEof),
+        LimitExceeded(LimitExceeded),
+        UnknownDiscriminant(UnknownDiscriminant),
+        MagicMismatch(MagicMismatch),
+        CapacityExceeded(CapacityExceeded),
+    }
+
+    /// Error type used without the `std` feature, standing in for `std::io::Error`
+    ///
+    /// Like `std::io::Error`, it cannot be cloned or compared: compare its `kind()` and payload.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        payload: Payload,
+    }
+
+    impl Error {
+        /// Creates an error from a kind and a message
+        pub fn new(kind: ErrorKind, msg: &'static str) -> Error {
+            Error::with_payload(kind, Payload::Message(msg))
+        }
+
+        pub(crate) fn with_payload(kind: ErrorKind, payload: Payload) -> Error {
+            Error { kind, payload }
+        }
+
+        /// The kind of the error
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+
+        /// The error itself if it carries a payload, mirroring `std::io::Error::get_ref`
+        ///
+        /// This keeps `err.get_ref().and_then(|e| e.downcast_ref::<T>())` working in both modes.
+        pub fn get_ref(&self) -> Option<&Error> {
+            match self.payload {
+                Payload::Message(_) => None,
+                _ => Some(self),
+            }
+        }
+
+        /// The error payload, such as an `UnexpectedEof`, if it has type `T`
+        pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+            let payload: &dyn Any = match self.payload {
+                Payload::Message(ref msg) => msg,
+                Payload::UnexpectedEof(ref e) => e,
+                Payload::LimitExceeded(ref e) => e,
+                Payload::UnknownDiscriminant(ref e) => e,
+                Payload::MagicMismatch(ref e) => e,
+                Payload::CapacityExceeded(ref e) => e,
+            };
+            payload.downcast_ref()
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.payload {
+                Payload::Message(msg) => f.write_str(msg),
+                Payload::UnexpectedEof(ref e) => e.fmt(f),
+                Payload::LimitExceeded(ref e) => e.fmt(f),
+                Payload::UnknownDiscriminant(ref e) => e.fmt(f),
+                Payload::MagicMismatch(ref e) => e.fmt(f),
+                Payload::CapacityExceeded(ref e) => e.fmt(f),
+            }
+        }
+    }
+
+    impl PodRead for &[u8] {
+        fn pod_read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let len = cmp::min(buf.len(), self.len());
+            let (head, tail) = self.split_at(len);
+            buf[..len].copy_from_slice(head);
+            *self = tail;
+            Ok(len)
+        }
+    }
+
+    impl<R: PodRead + ?Sized> PodRead for &mut R {
+        fn pod_read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            (**self).pod_read(buf)
+        }
+    }
+
+    impl PodWrite for Vec<u8> {
+        fn pod_write_all(&mut self, buf: &[u8]) -> Result<()> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    impl PodWrite for &mut [u8] {
+        fn pod_write_all(&mut self, buf: &[u8]) -> Result<()> {
+            // Like `std`, write as much as fits before failing
+            let len = cmp::min(buf.len(), self.len());
+            let (head, tail) = ::core::mem::take(self).split_at_mut(len);
+            head.copy_from_slice(&buf[..len]);
+            *self = tail;
+            if len < buf.len() {
+                return Err(Error::new(ErrorKind::WriteZero, "failed to write whole buffer"));
+            }
+            Ok(())
+        }
+    }
+
+    impl<W: PodWrite + ?Sized> PodWrite for &mut W {
+        fn pod_write_all(&mut self, buf: &[u8]) -> Result<()> {
+            (**self).pod_write_all(buf)
+        }
+    }
+}
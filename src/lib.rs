@@ -39,6 +39,207 @@
 //! assert_eq!(writer.into_inner(), &[0x02, 0x88]);
 //! ```
 //!
+//! ## Runtime endianness
+//!
+//! When the byte order is only known after parsing a header, the `_dyn` methods take an
+//! `Endian` value instead of a type parameter.
+//!
+//! ```
+//! use podio::{ReadPodExt, Endian};
+//!
+//! let slice: &[u8] = &[b'I', b'I', 0x2A, 0x00];
+//! let mut reader = std::io::Cursor::new(slice);
+//!
+//! let endian = match &reader.read_exact(2).unwrap()[..] {
+//!     b"II" => Endian::Little,
+//!     _ => Endian::Big,
+//! };
+//!
+//! assert_eq!(reader.read_u16_dyn(endian).unwrap(), 42);
+//! ```
+//!
+//! ## Bulk reads
+//!
+//! Slices of numbers can be filled with a single read, after which the values are byte swapped
+//! in place if needed. If an error is returned, the contents of the slice are unspecified.
+//!
+//! ```
+//! use podio::{ReadPodExt, LittleEndian};
+//!
+//! let slice: &[u8] = &[0x01, 0x00, 0x02, 0x00, 0x03, 0x00];
+//! let mut reader = std::io::Cursor::new(slice);
+//! let mut samples = [0u16; 3];
+//!
+//! reader.read_u16_into::<LittleEndian>(&mut samples).unwrap();
+//!
+//! assert_eq!(samples, [1, 2, 3]);
+//! ```
+//!
+//! Slices can likewise be written with a few calls to `write_all`.
+//!
+//! ```
+//! use podio::{WritePodExt, BigEndian};
+//!
+//! let mut out = Vec::new();
+//!
+//! out.write_u16_slice::<BigEndian>(&[1, 2, 3]).unwrap();
+//!
+//! assert_eq!(out, [0x00, 0x01, 0x00, 0x02, 0x00, 0x03]);
+//! ```
+//!
+//! ## Variable-length integers
+//!
+//! LEB128 values, as used by DWARF and WebAssembly, are read and written byte by byte. Values
+//! that do not fit in 64 bits are rejected with `io::ErrorKind::InvalidData`.
+//!
+//! ```
+//! use podio::{ReadPodExt, WritePodExt};
+//!
+//! let mut out = Vec::new();
+//! out.write_uleb128(624485).unwrap();
+//! out.write_sleb128(-123456).unwrap();
+//! assert_eq!(out, [0xE5, 0x8E, 0x26, 0xC0, 0xBB, 0x78]);
+//!
+//! let mut reader = std::io::Cursor::new(out);
+//! assert_eq!(reader.read_uleb128().unwrap(), 624485);
+//! assert_eq!(reader.read_sleb128().unwrap(), -123456);
+//! ```
+//!
+//! ## Length-prefixed bytes
+//!
+//! Byte strings stored after their length can be read and written with the type of the
+//! length field as a type parameter. Reading takes a maximum length, so a corrupt length
+//! field does not lead to a huge allocation.
+//!
+//! ```
+//! use podio::{ReadPodExt, WritePodExt, BigEndian};
+//!
+//! let mut out = Vec::new();
+//! out.write_prefixed_bytes::<u16, BigEndian>(b"podio").unwrap();
+//! assert_eq!(out, b"\x00\x05podio");
+//!
+//! let mut reader = std::io::Cursor::new(out);
+//! assert_eq!(reader.read_prefixed_bytes::<u16, BigEndian>(1024).unwrap(), b"podio");
+//! ```
+//!
+//! ## Generic values
+//!
+//! Generic code can read and write any type implementing `ReadPod` and `WritePod`, which
+//! covers all primitive integers and floats.
+//!
+//! ```
+//! use podio::{ReadPodExt, WritePodExt, ReadPod, WritePod, LittleEndian};
+//!
+//! fn roundtrip<P: ReadPod + WritePod>(val: P) -> P {
+//!     let mut out = Vec::new();
+//!     out.write_pod::<LittleEndian>(&val).unwrap();
+//!     (&out[..]).read_pod::<P, LittleEndian>().unwrap()
+//! }
+//!
+//! assert_eq!(roundtrip(0x1234u16), 0x1234);
+//! assert_eq!(roundtrip(-1.5f64), -1.5);
+//! ```
+//!
+//! With the `derive` feature enabled, `#[derive(Pod)]` implements both traits for a struct by
+//! reading and writing its fields in order. The endianness can be fixed for the whole struct
+//! or for single fields with `#[podio(endian = "le")]` or `"be"`.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use podio::{ReadPodExt, BigEndian, Pod};
+//!
+//! #[derive(Pod)]
+//! struct Header {
+//!     version: u16,
+//!     #[podio(endian = "le")]
+//!     flags: u32,
+//! }
+//!
+//! let mut reader: &[u8] = &[0x00, 0x02, 0x01, 0x00, 0x00, 0x00];
+//! let header = reader.read_pod::<Header, BigEndian>().unwrap();
+//!
+//! assert_eq!(header.version, 2);
+//! assert_eq!(header.flags, 1);
+//! # }
+//! ```
+//!
+//! ## Constants
+//!
+//! The fixed-width conversions are also available as `const fn`s, such as `encode_u32_be` and
+//! `decode_u16_le`, for building tables and headers at compile time.
+//!
+//! ```
+//! use podio::{decode_u16_le, encode_u32_be};
+//!
+//! const MAGIC: [u8; 4] = encode_u32_be(0xcafebabe);
+//! const VERSION: u16 = decode_u16_le([0x34, 0x00]);
+//!
+//! assert_eq!(MAGIC, [0xca, 0xfe, 0xba, 0xbe]);
+//! assert_eq!(VERSION, 52);
+//! ```
+//!
+//! ## In-memory buffers
+//!
+//! `SliceReader` reads from a byte slice without going through `io::Read`. Its errors are a
+//! small `OutOfBounds` value holding the failing offset, and `read_bytes` borrows from the slice.
+//!
+//! ```
+//! use podio::{BigEndian, SliceReader};
+//!
+//! let mut reader = SliceReader::new(&[0x00, 0x02, 0xca, 0xfe]);
+//! let len = reader.read_u16::<BigEndian>().unwrap();
+//! assert_eq!(reader.read_bytes(len as usize), Ok(&[0xca, 0xfe][..]));
+//! assert_eq!(reader.read_u8().unwrap_err().offset(), 4);
+//! ```
+//!
+//! `SliceWriter` and `ArrayWriter` write into a borrowed slice or an owned array. They
+//! implement `WritePodExt`, and report a `CapacityExceeded` payload when a value does not fit.
+//! Only a single write call is atomic: methods that write in several parts may leave some of
+//! them written.
+//!
+//! ```
+//! use podio::{ArrayWriter, BigEndian, WritePodExt};
+//!
+//! let mut writer = ArrayWriter::<4>::new();
+//! writer.write_u16::<BigEndian>(2).unwrap();
+//! writer.write_bytes(&[0xca, 0xfe]).unwrap();
+//! assert_eq!(writer.written(), &[0x00, 0x02, 0xca, 0xfe]);
+//! ```
+//!
+//! ## Storage types
+//!
+//! `U16<E>`, `U32<E>`, `F64<E>` and friends hold a value as bytes in the endianness `E`. They
+//! have an alignment of 1, so a `#[repr(C)]` struct of them can describe a binary header.
+//!
+//! ```
+//! use podio::{BigEndian, LittleEndian, U16, U32};
+//!
+//! #[repr(C)]
+//! struct Header {
+//!     magic: U32<BigEndian>,
+//!     version: U16<LittleEndian>,
+//! }
+//!
+//! let mut header = Header { magic: U32::new(0xcafebabe), version: U16::new(1) };
+//! header.version.set(2);
+//! assert_eq!(header.magic.to_bytes(), [0xca, 0xfe, 0xba, 0xbe]);
+//! assert_eq!(header.version.get(), 2);
+//! ```
+//!
+//! ## Without `std`
+//!
+//! The crate only needs `core` and `alloc` when its default `std` feature is disabled. The
+//! extension traits are then implemented over the minimal `PodRead` and `PodWrite` traits
+//! (for `&[u8]`, `&mut [u8]` and `Vec<u8>`), and errors use podio's own `Error` type. With
+//! `std`, `Error` and `Result` are those of `std::io`, and every `io::Read` and `io::Write`
+//! implements `PodRead` and `PodWrite`.
+//!
+//! Since enabling `std` anywhere in a build swaps these types, only the API the two have in
+//! common is stable: `Error::new`, `kind()`, `get_ref()` and `downcast_ref()`, and the
+//! `ErrorKind` variants of the `no_std` enum, which is `#[non_exhaustive]` like its `std`
+//! counterpart.
+//!
 //! ## Read exact
 //!
 //! One additional method, not really dealing with POD, is `read_exact`.
@@ -56,69 +257,351 @@
 //! assert!(reader.read_exact(1).is_err());
 
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "derive")]
+extern crate podio_derive;
+
+use alloc::ffi::CString;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp;
+use core::ffi::CStr;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+mod codec;
+mod core_io;
+mod pod;
+mod prefix;
+mod slice;
+mod storage;
+mod string;
+mod varint;
+
+pub use codec::{decode_u16_be, decode_u16_le, decode_u32_be, decode_u32_le, decode_u64_be,
+                decode_u64_le, decode_u128_be, decode_u128_le, decode_i16_be, decode_i16_le,
+                decode_i32_be, decode_i32_le, decode_i64_be, decode_i64_le, decode_i128_be,
+                decode_i128_le, decode_f32_be, decode_f32_le, decode_f64_be, decode_f64_le,
+                encode_u16_be, encode_u16_le, encode_u32_be, encode_u32_le, encode_u64_be,
+                encode_u64_le, encode_u128_be, encode_u128_le, encode_i16_be, encode_i16_le,
+                encode_i32_be, encode_i32_le, encode_i64_be, encode_i64_le, encode_i128_be,
+                encode_i128_le, encode_f32_be, encode_f32_le, encode_f64_be, encode_f64_le};
+pub use core_io::{Error, ErrorKind, PodRead, PodWrite, Result};
+pub use pod::{ReadPod, WritePod};
+#[cfg(feature = "derive")]
+pub use podio_derive::Pod;
+pub use prefix::{LengthPrefix, Uleb128};
+pub use slice::{ArrayWriter, CapacityExceeded, OutOfBounds, SliceReader, SliceWriter};
+pub use storage::{F32, F64, I128, I16, I32, I64, U128, U16, U32, U64};
+
+/// Items used by the code generated by `#[derive(Pod)]`, which may be used without `std`
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+    pub use core::cmp::min;
+    pub use core::convert::TryFrom;
+    pub use core::default::Default;
+    pub use core::option::Option::Some;
+    pub use core::result::Result::{Err, Ok};
+
+    use {PodRead, PodWrite, Result};
+
+    /// Chunk size for skipping and writing `#[podio(pad)]` bytes, kept small for the stack
+    const PAD_CHUNK_SIZE: usize = 64;
+
+    /// Read exactly `buf.len()` bytes, such as a `#[podio(magic)]` value
+    pub fn read_bytes<R: PodRead>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+        ::fill_buf(reader, buf)
+    }
+
+    /// Read and discard `len` bytes
+    pub fn skip_pad<R: PodRead>(reader: &mut R, len: usize) -> Result<()> {
+        let mut buf = [0u8; PAD_CHUNK_SIZE];
+        let mut done = 0;
+        while done < len {
+            let chunk = min(len - done, PAD_CHUNK_SIZE);
+            let read = ::read_full(reader, &mut buf[..chunk])?;
+            done += read;
+            if read != chunk {
+                return Err(::unexpected_eof(done, len));
+            }
+        }
+        Ok(())
+    }
 
-use std::io;
-use std::io::prelude::*;
+    /// Write `len` zero bytes
+    pub fn write_pad<W: PodWrite>(writer: &mut W, len: usize) -> Result<()> {
+        let buf = [0u8; PAD_CHUNK_SIZE];
+        let mut done = 0;
+        while done < len {
+            let chunk = min(len - done, PAD_CHUNK_SIZE);
+            writer.pod_write_all(&buf[..chunk])?;
+            done += chunk;
+        }
+        Ok(())
+    }
+}
 
 /// Little endian. The number `0xABCD` is stored `[0xCD, 0xAB]`
 pub enum LittleEndian {}
 /// Big endian. The number `0xABCD` is stored `[0xAB, 0xCD]`
 pub enum BigEndian {}
 
+/// Network byte order, which is big endian
+pub type NetworkEndian = BigEndian;
+
+/// The endianness of the target the crate is compiled for
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+/// The endianness of the target the crate is compiled for
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+/// Endianness selected at runtime, for formats that declare their byte order in a header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Little endian, see `LittleEndian`
+    Little,
+    /// Big endian, see `BigEndian`
+    Big,
+}
+
 /// Trait to determine the conversion methods for a specific endianness
 pub trait Endianness {
     /// Converts a value between little-endian and the specified endianness
     fn is_little_endian() -> bool;
 }
 
-/// Additional write methods for a io::Write
+/// Additional write methods for a `PodWrite`, such as any `io::Write`
 pub trait WritePodExt {
+    /// Write a u128
+    fn write_u128<T: Endianness>(&mut self, val: u128) -> Result<()>;
     /// Write a u64
-    fn write_u64<T: Endianness>(&mut self, u64) -> io::Result<()>;
+    fn write_u64<T: Endianness>(&mut self, val: u64) -> Result<()>;
     /// Write a u32
-    fn write_u32<T: Endianness>(&mut self, u32) -> io::Result<()>;
+    fn write_u32<T: Endianness>(&mut self, val: u32) -> Result<()>;
     /// Write a u16
-    fn write_u16<T: Endianness>(&mut self, u16) -> io::Result<()>;
+    fn write_u16<T: Endianness>(&mut self, val: u16) -> Result<()>;
     /// Write a u8
-    fn write_u8(&mut self, u8) -> io::Result<()>;
+    fn write_u8(&mut self, val: u8) -> Result<()>;
+    /// Write a i128
+    fn write_i128<T: Endianness>(&mut self, val: i128) -> Result<()>;
     /// Write a i64
-    fn write_i64<T: Endianness>(&mut self, i64) -> io::Result<()>;
+    fn write_i64<T: Endianness>(&mut self, val: i64) -> Result<()>;
     /// Write a i32
-    fn write_i32<T: Endianness>(&mut self, i32) -> io::Result<()>;
+    fn write_i32<T: Endianness>(&mut self, val: i32) -> Result<()>;
     /// Write a i16
-    fn write_i16<T: Endianness>(&mut self, i16) -> io::Result<()>;
+    fn write_i16<T: Endianness>(&mut self, val: i16) -> Result<()>;
     /// Write a i8
-    fn write_i8(&mut self, i8) -> io::Result<()>;
+    fn write_i8(&mut self, val: i8) -> Result<()>;
     /// Write a f32
-    fn write_f32<T: Endianness>(&mut self, f32) -> io::Result<()>;
+    fn write_f32<T: Endianness>(&mut self, val: f32) -> Result<()>;
     /// Write a f64
-    fn write_f64<T: Endianness>(&mut self, f64) -> io::Result<()>;
+    fn write_f64<T: Endianness>(&mut self, val: f64) -> Result<()>;
+    /// Write a slice of u128 values, converted in chunks
+    fn write_u128_slice<T: Endianness>(&mut self, vals: &[u128]) -> Result<()>;
+    /// Write a slice of u64 values, converted in chunks
+    fn write_u64_slice<T: Endianness>(&mut self, vals: &[u64]) -> Result<()>;
+    /// Write a slice of u32 values, converted in chunks
+    fn write_u32_slice<T: Endianness>(&mut self, vals: &[u32]) -> Result<()>;
+    /// Write a slice of u16 values, converted in chunks
+    fn write_u16_slice<T: Endianness>(&mut self, vals: &[u16]) -> Result<()>;
+    /// Write a slice of u8 values
+    fn write_u8_slice(&mut self, vals: &[u8]) -> Result<()>;
+    /// Write a slice of i128 values, converted in chunks
+    fn write_i128_slice<T: Endianness>(&mut self, vals: &[i128]) -> Result<()>;
+    /// Write a slice of i64 values, converted in chunks
+    fn write_i64_slice<T: Endianness>(&mut self, vals: &[i64]) -> Result<()>;
+    /// Write a slice of i32 values, converted in chunks
+    fn write_i32_slice<T: Endianness>(&mut self, vals: &[i32]) -> Result<()>;
+    /// Write a slice of i16 values, converted in chunks
+    fn write_i16_slice<T: Endianness>(&mut self, vals: &[i16]) -> Result<()>;
+    /// Write a slice of i8 values, converted in chunks
+    fn write_i8_slice(&mut self, vals: &[i8]) -> Result<()>;
+    /// Write a slice of f32 values, converted in chunks
+    fn write_f32_slice<T: Endianness>(&mut self, vals: &[f32]) -> Result<()>;
+    /// Write a slice of f64 values, converted in chunks
+    fn write_f64_slice<T: Endianness>(&mut self, vals: &[f64]) -> Result<()>;
+    /// Write a u128 in the endianness given at runtime
+    fn write_u128_dyn(&mut self, endian: Endian, val: u128) -> Result<()>;
+    /// Write a u64 in the endianness given at runtime
+    fn write_u64_dyn(&mut self, endian: Endian, val: u64) -> Result<()>;
+    /// Write a u32 in the endianness given at runtime
+    fn write_u32_dyn(&mut self, endian: Endian, val: u32) -> Result<()>;
+    /// Write a u16 in the endianness given at runtime
+    fn write_u16_dyn(&mut self, endian: Endian, val: u16) -> Result<()>;
+    /// Write a i128 in the endianness given at runtime
+    fn write_i128_dyn(&mut self, endian: Endian, val: i128) -> Result<()>;
+    /// Write a i64 in the endianness given at runtime
+    fn write_i64_dyn(&mut self, endian: Endian, val: i64) -> Result<()>;
+    /// Write a i32 in the endianness given at runtime
+    fn write_i32_dyn(&mut self, endian: Endian, val: i32) -> Result<()>;
+    /// Write a i16 in the endianness given at runtime
+    fn write_i16_dyn(&mut self, endian: Endian, val: i16) -> Result<()>;
+    /// Write a f32 in the endianness given at runtime
+    fn write_f32_dyn(&mut self, endian: Endian, val: f32) -> Result<()>;
+    /// Write a f64 in the endianness given at runtime
+    fn write_f64_dyn(&mut self, endian: Endian, val: f64) -> Result<()>;
+    /// Write a u64 as unsigned LEB128
+    fn write_uleb128(&mut self, val: u64) -> Result<()>;
+    /// Write a i64 as signed LEB128
+    fn write_sleb128(&mut self, val: i64) -> Result<()>;
+    /// Write a i32 as a zigzag encoded varint, as used by Protocol Buffers
+    fn write_zigzag_i32(&mut self, val: i32) -> Result<()>;
+    /// Write a i64 as a zigzag encoded varint, as used by Protocol Buffers
+    fn write_zigzag_i64(&mut self, val: i64) -> Result<()>;
+    /// Write a QUIC variable-length integer (RFC 9000) in its shortest form
+    ///
+    /// Values of 2^62 and above cannot be encoded and give an `io::ErrorKind::InvalidInput`.
+    fn write_quic_varint(&mut self, val: u64) -> Result<()>;
+    /// Write a byte string preceded by its length
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput`, without writing anything, if the length does
+    /// not fit in the prefix.
+    fn write_prefixed_bytes<P: LengthPrefix, E: Endianness>(&mut self, bytes: &[u8]) -> Result<()>;
+    /// Write a C string, including its NUL terminator
+    fn write_cstring(&mut self, val: &CStr) -> Result<()>;
+    /// Write a string into a field of `width` bytes, filling the remainder with `pad`
+    ///
+    /// A string longer than the field fails with `io::ErrorKind::InvalidInput`, without
+    /// writing anything.
+    fn write_fixed_str(&mut self, val: &str, width: usize, pad: u8) -> Result<()>;
+    /// Write a string as UTF-16 code units
+    fn write_utf16<T: Endianness>(&mut self, val: &str) -> Result<()>;
+    /// Write a string as UTF-16 code units, followed by a NUL code unit
+    ///
+    /// A string containing a NUL character fails with `io::ErrorKind::InvalidInput`, without
+    /// writing anything.
+    fn write_utf16_nul<T: Endianness>(&mut self, val: &str) -> Result<()>;
+    /// Write any value implementing `WritePod`
+    fn write_pod<T: Endianness>(&mut self, val: &impl WritePod) -> Result<()>;
 }
 
-/// Additional read methods for a io::Read
+/// Additional read methods for a `PodRead`, such as any `io::Read`
 pub trait ReadPodExt {
+    /// Read a u128
+    fn read_u128<T: Endianness>(&mut self) -> Result<u128>;
     /// Read a u64
-    fn read_u64<T: Endianness>(&mut self) -> io::Result<u64>;
+    fn read_u64<T: Endianness>(&mut self) -> Result<u64>;
     /// Read a u32
-    fn read_u32<T: Endianness>(&mut self) -> io::Result<u32>;
+    fn read_u32<T: Endianness>(&mut self) -> Result<u32>;
     /// Read a u16
-    fn read_u16<T: Endianness>(&mut self) -> io::Result<u16>;
+    fn read_u16<T: Endianness>(&mut self) -> Result<u16>;
     /// Read a u8
-    fn read_u8(&mut self) -> io::Result<u8>;
+    fn read_u8(&mut self) -> Result<u8>;
+    /// Read a i128
+    fn read_i128<T: Endianness>(&mut self) -> Result<i128>;
     /// Read a i64
-    fn read_i64<T: Endianness>(&mut self) -> io::Result<i64>;
+    fn read_i64<T: Endianness>(&mut self) -> Result<i64>;
     /// Read a i32
-    fn read_i32<T: Endianness>(&mut self) -> io::Result<i32>;
+    fn read_i32<T: Endianness>(&mut self) -> Result<i32>;
     /// Read a i16
-    fn read_i16<T: Endianness>(&mut self) -> io::Result<i16>;
+    fn read_i16<T: Endianness>(&mut self) -> Result<i16>;
     /// Read a i8
-    fn read_i8(&mut self) -> io::Result<i8>;
+    fn read_i8(&mut self) -> Result<i8>;
     /// Read a f32
-    fn read_f32<T: Endianness>(&mut self) -> io::Result<f32>;
+    fn read_f32<T: Endianness>(&mut self) -> Result<f32>;
     /// Read a f64
-    fn read_f64<T: Endianness>(&mut self) -> io::Result<f64>;
+    fn read_f64<T: Endianness>(&mut self) -> Result<f64>;
+    /// Read a u128 in the endianness given at runtime
+    fn read_u128_dyn(&mut self, endian: Endian) -> Result<u128>;
+    /// Read a u64 in the endianness given at runtime
+    fn read_u64_dyn(&mut self, endian: Endian) -> Result<u64>;
+    /// Read a u32 in the endianness given at runtime
+    fn read_u32_dyn(&mut self, endian: Endian) -> Result<u32>;
+    /// Read a u16 in the endianness given at runtime
+    fn read_u16_dyn(&mut self, endian: Endian) -> Result<u16>;
+    /// Read a i128 in the endianness given at runtime
+    fn read_i128_dyn(&mut self, endian: Endian) -> Result<i128>;
+    /// Read a i64 in the endianness given at runtime
+    fn read_i64_dyn(&mut self, endian: Endian) -> Result<i64>;
+    /// Read a i32 in the endianness given at runtime
+    fn read_i32_dyn(&mut self, endian: Endian) -> Result<i32>;
+    /// Read a i16 in the endianness given at runtime
+    fn read_i16_dyn(&mut self, endian: Endian) -> Result<i16>;
+    /// Read a f32 in the endianness given at runtime
+    fn read_f32_dyn(&mut self, endian: Endian) -> Result<f32>;
+    /// Read a f64 in the endianness given at runtime
+    fn read_f64_dyn(&mut self, endian: Endian) -> Result<f64>;
+    /// Read an unsigned LEB128 value
+    fn read_uleb128(&mut self) -> Result<u64>;
+    /// Read an unsigned LEB128 value, rejecting overlong encodings
+    fn read_uleb128_strict(&mut self) -> Result<u64>;
+    /// Read a signed LEB128 value
+    fn read_sleb128(&mut self) -> Result<i64>;
+    /// Read a signed LEB128 value, rejecting overlong encodings
+    fn read_sleb128_strict(&mut self) -> Result<i64>;
+    /// Read a zigzag encoded varint into a i32
+    fn read_zigzag_i32(&mut self) -> Result<i32>;
+    /// Read a zigzag encoded varint into a i64
+    fn read_zigzag_i64(&mut self) -> Result<i64>;
+    /// Read a QUIC variable-length integer (RFC 9000)
+    fn read_quic_varint(&mut self) -> Result<u64>;
+    /// Read a QUIC variable-length integer (RFC 9000), rejecting non-minimal encodings
+    fn read_quic_varint_strict(&mut self) -> Result<u64>;
+    /// Read enough u128 values to fill `dst` with a single bulk read
+    fn read_u128_into<T: Endianness>(&mut self, dst: &mut [u128]) -> Result<()>;
+    /// Read enough u64 values to fill `dst` with a single bulk read
+    fn read_u64_into<T: Endianness>(&mut self, dst: &mut [u64]) -> Result<()>;
+    /// Read enough u32 values to fill `dst` with a single bulk read
+    fn read_u32_into<T: Endianness>(&mut self, dst: &mut [u32]) -> Result<()>;
+    /// Read enough u16 values to fill `dst` with a single bulk read
+    fn read_u16_into<T: Endianness>(&mut self, dst: &mut [u16]) -> Result<()>;
+    /// Read enough i128 values to fill `dst` with a single bulk read
+    fn read_i128_into<T: Endianness>(&mut self, dst: &mut [i128]) -> Result<()>;
+    /// Read enough i64 values to fill `dst` with a single bulk read
+    fn read_i64_into<T: Endianness>(&mut self, dst: &mut [i64]) -> Result<()>;
+    /// Read enough i32 values to fill `dst` with a single bulk read
+    fn read_i32_into<T: Endianness>(&mut self, dst: &mut [i32]) -> Result<()>;
+    /// Read enough i16 values to fill `dst` with a single bulk read
+    fn read_i16_into<T: Endianness>(&mut self, dst: &mut [i16]) -> Result<()>;
+    /// Read enough f32 values to fill `dst` with a single bulk read
+    fn read_f32_into<T: Endianness>(&mut self, dst: &mut [f32]) -> Result<()>;
+    /// Read enough f64 values to fill `dst` with a single bulk read
+    fn read_f64_into<T: Endianness>(&mut self, dst: &mut [f64]) -> Result<()>;
     /// Read a specific number of bytes
-    fn read_exact(&mut self, usize) -> io::Result<Vec<u8>>;
+    ///
+    /// The buffer grows as data arrives, so a large `len` on a short stream fails with
+    /// `io::ErrorKind::UnexpectedEof` without allocating `len` bytes first.
+    fn read_exact(&mut self, len: usize) -> Result<Vec<u8>>;
+    /// Read a specific number of bytes, refusing lengths above `limit`
+    ///
+    /// A `len` larger than `limit` fails with `io::ErrorKind::InvalidData` carrying a
+    /// `LimitExceeded`, without reading anything.
+    fn read_exact_limited(&mut self, len: usize, limit: usize) -> Result<Vec<u8>>;
+    /// Read a byte string preceded by its length
+    ///
+    /// A length larger than `max_len` is rejected with `io::ErrorKind::InvalidData` carrying a
+    /// `LimitExceeded`, before anything is allocated.
+    fn read_prefixed_bytes<P: LengthPrefix, E: Endianness>(&mut self, max_len: usize) -> Result<Vec<u8>>;
+    /// Read a NUL-terminated string of at most `max_len` bytes, not counting the terminator
+    ///
+    /// A longer string fails with `io::ErrorKind::InvalidData` carrying a `LimitExceeded` whose
+    /// `requested()` is `max_len + 1`, as reading stops there. A string cut off by the end of the
+    /// stream fails with `io::ErrorKind::UnexpectedEof`, whose `UnexpectedEof` payload counts
+    /// the bytes of the string read so far and expects one more.
+    fn read_cstring(&mut self, max_len: usize) -> Result<CString>;
+    /// Read a UTF-8 string from a field of `width` bytes, stripping trailing `pad` bytes
+    fn read_fixed_str(&mut self, width: usize, pad: u8) -> Result<String>;
+    /// Read a string of `len` UTF-16 code units
+    ///
+    /// Unpaired surrogates fail with `io::ErrorKind::InvalidData`.
+    fn read_utf16<T: Endianness>(&mut self, len: usize) -> Result<String>;
+    /// Read a string of `len` UTF-16 code units, replacing unpaired surrogates with U+FFFD
+    fn read_utf16_lossy<T: Endianness>(&mut self, len: usize) -> Result<String>;
+    /// Read a NUL-terminated UTF-16 string of at most `max_len` code units, not counting the terminator
+    ///
+    /// Errors are as for `read_cstring` and `read_utf16`.
+    fn read_utf16_nul<T: Endianness>(&mut self, max_len: usize) -> Result<String>;
+    /// Read a NUL-terminated UTF-16 string, replacing unpaired surrogates with U+FFFD
+    fn read_utf16_nul_lossy<T: Endianness>(&mut self, max_len: usize) -> Result<String>;
+    /// Read any value implementing `ReadPod`
+    fn read_pod<P: ReadPod, T: Endianness>(&mut self) -> Result<P>;
 }
 
 impl Endianness for LittleEndian {
@@ -133,140 +616,834 @@ impl Endianness for BigEndian {
     }
 }
 
-impl<W: Write> WritePodExt for W {
-    fn write_u64<T: Endianness>(&mut self, val: u64) -> io::Result<()> {
+impl<W: PodWrite> WritePodExt for W {
+    fn write_u128<T: Endianness>(&mut self, val: u128) -> Result<()> {
+        let buf = match <T as Endianness>::is_little_endian() {
+            true => encode_u128_le(val),
+            false => encode_u128_be(val),
+        };
+        self.pod_write_all(&buf)
+    }
+
+    fn write_u64<T: Endianness>(&mut self, val: u64) -> Result<()> {
         let buf = match <T as Endianness>::is_little_endian() {
-            true => u64::to_le_bytes(val),
-            false => u64::to_be_bytes(val),
+            true => encode_u64_le(val),
+            false => encode_u64_be(val),
         };
-        self.write_all(&buf)
+        self.pod_write_all(&buf)
     }
 
-    fn write_u32<T: Endianness>(&mut self, val: u32) -> io::Result<()> {
+    fn write_u32<T: Endianness>(&mut self, val: u32) -> Result<()> {
         let buf = match <T as Endianness>::is_little_endian() {
-            true => u32::to_le_bytes(val),
-            false => u32::to_be_bytes(val),
+            true => encode_u32_le(val),
+            false => encode_u32_be(val),
         };
-        self.write_all(&buf)
+        self.pod_write_all(&buf)
     }
 
-    fn write_u16<T: Endianness>(&mut self, val: u16) -> io::Result<()> {
+    fn write_u16<T: Endianness>(&mut self, val: u16) -> Result<()> {
         let buf = match <T as Endianness>::is_little_endian() {
-            true => u16::to_le_bytes(val),
-            false => u16::to_be_bytes(val),
+            true => encode_u16_le(val),
+            false => encode_u16_be(val),
         };
-        self.write_all(&buf)
+        self.pod_write_all(&buf)
+    }
+
+    fn write_u8(&mut self, val: u8) -> Result<()> {
+        self.pod_write_all(&[val])
     }
 
-    fn write_u8(&mut self, val: u8) -> io::Result<()> {
-        self.write_all(&[val])
+    fn write_i128<T: Endianness>(&mut self, val: i128) -> Result<()> {
+        self.write_u128::<T>(val as u128)
     }
 
-    fn write_i64<T: Endianness>(&mut self, val: i64) -> io::Result<()> {
+    fn write_i64<T: Endianness>(&mut self, val: i64) -> Result<()> {
         self.write_u64::<T>(val as u64)
     }
 
-    fn write_i32<T: Endianness>(&mut self, val: i32) -> io::Result<()> {
+    fn write_i32<T: Endianness>(&mut self, val: i32) -> Result<()> {
         self.write_u32::<T>(val as u32)
     }
 
-    fn write_i16<T: Endianness>(&mut self, val: i16) -> io::Result<()> {
+    fn write_i16<T: Endianness>(&mut self, val: i16) -> Result<()> {
         self.write_u16::<T>(val as u16)
     }
 
-    fn write_i8(&mut self, val: i8) -> io::Result<()> {
+    fn write_i8(&mut self, val: i8) -> Result<()> {
         self.write_u8(val as u8)
     }
 
-    fn write_f32<T: Endianness>(&mut self, val: f32) -> io::Result<()> {
+    fn write_f32<T: Endianness>(&mut self, val: f32) -> Result<()> {
         let tval: u32 = val.to_bits();
         self.write_u32::<T>(tval)
     }
 
-    fn write_f64<T: Endianness>(&mut self, val: f64) -> io::Result<()> {
+    fn write_f64<T: Endianness>(&mut self, val: f64) -> Result<()> {
         let tval: u64 = val.to_bits();
         self.write_u64::<T>(tval)
     }
+
+    fn write_u128_slice<T: Endianness>(&mut self, vals: &[u128]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_u128_le),
+            false => write_chunked(self, vals, encode_u128_be),
+        }
+    }
+
+    fn write_u64_slice<T: Endianness>(&mut self, vals: &[u64]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_u64_le),
+            false => write_chunked(self, vals, encode_u64_be),
+        }
+    }
+
+    fn write_u32_slice<T: Endianness>(&mut self, vals: &[u32]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_u32_le),
+            false => write_chunked(self, vals, encode_u32_be),
+        }
+    }
+
+    fn write_u16_slice<T: Endianness>(&mut self, vals: &[u16]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_u16_le),
+            false => write_chunked(self, vals, encode_u16_be),
+        }
+    }
+
+    fn write_u8_slice(&mut self, vals: &[u8]) -> Result<()> {
+        self.pod_write_all(vals)
+    }
+
+    fn write_i128_slice<T: Endianness>(&mut self, vals: &[i128]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_i128_le),
+            false => write_chunked(self, vals, encode_i128_be),
+        }
+    }
+
+    fn write_i64_slice<T: Endianness>(&mut self, vals: &[i64]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_i64_le),
+            false => write_chunked(self, vals, encode_i64_be),
+        }
+    }
+
+    fn write_i32_slice<T: Endianness>(&mut self, vals: &[i32]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_i32_le),
+            false => write_chunked(self, vals, encode_i32_be),
+        }
+    }
+
+    fn write_i16_slice<T: Endianness>(&mut self, vals: &[i16]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_i16_le),
+            false => write_chunked(self, vals, encode_i16_be),
+        }
+    }
+
+    fn write_i8_slice(&mut self, vals: &[i8]) -> Result<()> {
+        write_chunked(self, vals, |v: i8| [v as u8])
+    }
+
+    fn write_f32_slice<T: Endianness>(&mut self, vals: &[f32]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_f32_le),
+            false => write_chunked(self, vals, encode_f32_be),
+        }
+    }
+
+    fn write_f64_slice<T: Endianness>(&mut self, vals: &[f64]) -> Result<()> {
+        match <T as Endianness>::is_little_endian() {
+            true => write_chunked(self, vals, encode_f64_le),
+            false => write_chunked(self, vals, encode_f64_be),
+        }
+    }
+
+    fn write_u128_dyn(&mut self, endian: Endian, val: u128) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_u128::<LittleEndian>(val),
+            Endian::Big => self.write_u128::<BigEndian>(val),
+        }
+    }
+
+    fn write_u64_dyn(&mut self, endian: Endian, val: u64) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_u64::<LittleEndian>(val),
+            Endian::Big => self.write_u64::<BigEndian>(val),
+        }
+    }
+
+    fn write_u32_dyn(&mut self, endian: Endian, val: u32) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_u32::<LittleEndian>(val),
+            Endian::Big => self.write_u32::<BigEndian>(val),
+        }
+    }
+
+    fn write_u16_dyn(&mut self, endian: Endian, val: u16) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_u16::<LittleEndian>(val),
+            Endian::Big => self.write_u16::<BigEndian>(val),
+        }
+    }
+
+    fn write_i128_dyn(&mut self, endian: Endian, val: i128) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_i128::<LittleEndian>(val),
+            Endian::Big => self.write_i128::<BigEndian>(val),
+        }
+    }
+
+    fn write_i64_dyn(&mut self, endian: Endian, val: i64) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_i64::<LittleEndian>(val),
+            Endian::Big => self.write_i64::<BigEndian>(val),
+        }
+    }
+
+    fn write_i32_dyn(&mut self, endian: Endian, val: i32) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_i32::<LittleEndian>(val),
+            Endian::Big => self.write_i32::<BigEndian>(val),
+        }
+    }
+
+    fn write_i16_dyn(&mut self, endian: Endian, val: i16) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_i16::<LittleEndian>(val),
+            Endian::Big => self.write_i16::<BigEndian>(val),
+        }
+    }
+
+    fn write_f32_dyn(&mut self, endian: Endian, val: f32) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_f32::<LittleEndian>(val),
+            Endian::Big => self.write_f32::<BigEndian>(val),
+        }
+    }
+
+    fn write_f64_dyn(&mut self, endian: Endian, val: f64) -> Result<()> {
+        match endian {
+            Endian::Little => self.write_f64::<LittleEndian>(val),
+            Endian::Big => self.write_f64::<BigEndian>(val),
+        }
+    }
+
+    fn write_uleb128(&mut self, val: u64) -> Result<()> {
+        varint::write_uleb128(self, val)
+    }
+
+    fn write_sleb128(&mut self, val: i64) -> Result<()> {
+        varint::write_sleb128(self, val)
+    }
+
+    fn write_zigzag_i32(&mut self, val: i32) -> Result<()> {
+        varint::write_zigzag_i32(self, val)
+    }
+
+    fn write_zigzag_i64(&mut self, val: i64) -> Result<()> {
+        varint::write_zigzag_i64(self, val)
+    }
+
+    fn write_quic_varint(&mut self, val: u64) -> Result<()> {
+        varint::write_quic_varint(self, val)
+    }
+
+    fn write_prefixed_bytes<P: LengthPrefix, E: Endianness>(&mut self, bytes: &[u8]) -> Result<()> {
+        P::write_len::<Self, E>(self, bytes.len())?;
+        self.pod_write_all(bytes)
+    }
+
+    fn write_cstring(&mut self, val: &CStr) -> Result<()> {
+        string::write_cstring(self, val)
+    }
+
+    fn write_fixed_str(&mut self, val: &str, width: usize, pad: u8) -> Result<()> {
+        string::write_fixed_str(self, val, width, pad)
+    }
+
+    fn write_utf16<T: Endianness>(&mut self, val: &str) -> Result<()> {
+        string::write_utf16::<Self, T>(self, val, false)
+    }
+
+    fn write_utf16_nul<T: Endianness>(&mut self, val: &str) -> Result<()> {
+        string::write_utf16::<Self, T>(self, val, true)
+    }
+
+    fn write_pod<T: Endianness>(&mut self, val: &impl WritePod) -> Result<()> {
+        val.write_to::<Self, T>(self)
+    }
+}
+
+/// Error payload for an `io::ErrorKind::UnexpectedEof` raised while reading a value
+///
+/// It records how many bytes were read before the end of the stream was reached, so a clean
+/// end-of-stream (no bytes read) can be told apart from a value that was cut off halfway.
+///
+/// ```
+/// use podio::{ReadPodExt, BigEndian, UnexpectedEof};
+///
+/// let mut reader: &[u8] = &[0x01, 0x02];
+/// let err = reader.read_u32::<BigEndian>().unwrap_err();
+///
+/// assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+/// let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap();
+/// assert_eq!(eof.bytes_read(), 2);
+/// assert_eq!(eof.bytes_expected(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnexpectedEof {
+    read: usize,
+    expected: usize,
+}
+
+impl UnexpectedEof {
+    /// Number of bytes read before the end of the stream
+    pub fn bytes_read(&self) -> usize {
+        self.read
+    }
+
+    /// Number of bytes that were requested
+    pub fn bytes_expected(&self) -> usize {
+        self.expected
+    }
+
+    /// Whether the stream ended before any byte of the value was read
+    pub fn is_clean(&self) -> bool {
+        self.read == 0
+    }
+}
+
+impl fmt::Display for UnexpectedEof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not read enough bytes: got {} of {}", self.read, self.expected)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for UnexpectedEof {}
+
+/// Error payload for an `io::ErrorKind::InvalidData` raised when a length exceeds the given limit
+///
+/// Also returned by the readers generated by `#[derive(Pod)]` for `Vec` fields with a maximum.
+///
+/// ```
+/// use podio::{ReadPodExt, LimitExceeded};
+///
+/// let mut reader: &[u8] = &[0; 16];
+/// let err = reader.read_exact_limited(32, 16).unwrap_err();
+///
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// let limit = err.get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()).unwrap();
+/// assert_eq!(limit.requested(), 32);
+/// assert_eq!(limit.limit(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    len: u64,
+    limit: usize,
+}
+
+impl LimitExceeded {
+    /// Creates the error for a `requested` length exceeding `limit`
+    pub fn new(requested: u64, limit: usize) -> LimitExceeded {
+        LimitExceeded { len: requested, limit }
+    }
+
+    /// The length that was requested or found in the input
+    ///
+    /// NUL-terminated strings are not scanned past the limit, so for them this is `limit + 1`
+    /// and means "at least this long".
+    pub fn requested(&self) -> u64 {
+        self.len
+    }
+
+    /// The maximum length that was allowed
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Length {} exceeds the limit of {}", self.len, self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for LimitExceeded {}
+
+/// Error payload for an `io::ErrorKind::InvalidData` raised when an enum is read from an
+/// unknown discriminant
+///
+/// Returned by the readers generated by `#[derive(Pod)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDiscriminant {
+    type_name: &'static str,
+    value: u64,
+}
+
+impl UnknownDiscriminant {
+    /// Creates the error for the enum `type_name` and the raw discriminant `value`
+    pub fn new(type_name: &'static str, value: u64) -> UnknownDiscriminant {
+        UnknownDiscriminant { type_name, value }
+    }
+
+    /// Name of the enum that was read
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The raw discriminant that was read
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl fmt::Display for UnknownDiscriminant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown discriminant {:#x} for {}", self.value, self.type_name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for UnknownDiscriminant {}
+
+/// Error payload for an `io::ErrorKind::InvalidData` raised when magic bytes do not match
+///
+/// Returned by the readers generated by `#[derive(Pod)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagicMismatch {
+    expected: &'static [u8],
+    found: Vec<u8>,
 }
 
+impl MagicMismatch {
+    /// Creates the error for the `expected` magic bytes and the bytes that were `found`
+    pub fn new(expected: &'static [u8], found: &[u8]) -> MagicMismatch {
+        MagicMismatch { expected, found: found.to_vec() }
+    }
+
+    /// The magic bytes that were expected
+    pub fn expected(&self) -> &'static [u8] {
+        self.expected
+    }
+
+    /// The bytes that were read instead
+    pub fn found(&self) -> &[u8] {
+        &self.found
+    }
+}
+
+impl fmt::Display for MagicMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Magic mismatch: expected {:02x?}, found {:02x?}", self.expected, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for MagicMismatch {}
+
+fn limit_exceeded(len: u64, limit: usize) -> Error {
+    LimitExceeded { len, limit }.into()
+}
+
+/// Amount by which `read_exact` grows its buffer at least, as long as data keeps arriving
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Size of the stack buffer used to convert slices before writing them
+const WRITE_CHUNK_SIZE: usize = 1024;
+
+fn write_chunked<W, N, F, const S: usize>(writer: &mut W, vals: &[N], to_bytes: F) -> Result<()>
+    where W: PodWrite, N: Copy, F: Fn(N) -> [u8; S]
+{
+    let mut buf = [0u8; WRITE_CHUNK_SIZE];
+    for chunk in vals.chunks(WRITE_CHUNK_SIZE / S) {
+        for (dst, &val) in buf.chunks_exact_mut(S).zip(chunk) {
+            dst.copy_from_slice(&to_bytes(val));
+        }
+        writer.pod_write_all(&buf[..chunk.len() * S])?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn needs_swap<T: Endianness>() -> bool {
+    <T as Endianness>::is_little_endian() != cfg!(target_endian = "little")
+}
+
+/// Views a slice of numbers as its underlying bytes
+///
+/// Only to be used with primitive integer and float types, for which every bit pattern is valid.
 #[inline]
-fn fill_buf<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<()> {
+unsafe fn slice_as_bytes_mut<N: Copy>(slice: &mut [N]) -> &mut [u8] {
+    core::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut u8, core::mem::size_of_val(slice))
+}
+
+fn invalid_data(msg: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+/// Reads until `buf` is full or the end of the stream is reached, returning the number of bytes read
+#[inline]
+fn read_full<R: PodRead>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
     let mut idx = 0;
     while idx != buf.len() {
-        match reader.read(&mut buf[idx..]) {
-            Ok(0) => return Err(io::Error::new(io::ErrorKind::Other, "Could not read enough bytes")),
-            Ok(v) => { idx += v; }
-            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
-            Err(e) => return Err(e),
+        match reader.pod_read(&mut buf[idx..])? {
+            0 => break,
+            v => { idx += v; }
         }
     }
+    Ok(idx)
+}
+
+fn unexpected_eof(read: usize, expected: usize) -> Error {
+    UnexpectedEof { read, expected }.into()
+}
+
+#[inline]
+fn fill_buf<R: PodRead>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+    let read = read_full(reader, buf)?;
+    if read != buf.len() {
+        return Err(unexpected_eof(read, buf.len()));
+    }
     Ok(())
 }
 
-impl<R: Read> ReadPodExt for R {
-    fn read_u64<T: Endianness>(&mut self) -> io::Result<u64> {
+impl<R: PodRead> ReadPodExt for R {
+    fn read_u128<T: Endianness>(&mut self) -> Result<u128> {
+        let mut buf = [0u8; 16];
+        fill_buf(self, &mut buf)?;
+        let val = match <T as Endianness>::is_little_endian() {
+            true => decode_u128_le(buf),
+            false => decode_u128_be(buf),
+        };
+        Ok(val)
+    }
+
+    fn read_u64<T: Endianness>(&mut self) -> Result<u64> {
         let mut buf = [0u8; 8];
         fill_buf(self, &mut buf)?;
         let val = match <T as Endianness>::is_little_endian() {
-            true => u64::from_le_bytes(buf),
-            false => u64::from_be_bytes(buf),
+            true => decode_u64_le(buf),
+            false => decode_u64_be(buf),
         };
         Ok(val)
     }
 
-    fn read_u32<T: Endianness>(&mut self) -> io::Result<u32> {
+    fn read_u32<T: Endianness>(&mut self) -> Result<u32> {
         let mut buf = [0u8; 4];
         fill_buf(self, &mut buf)?;
         let val = match <T as Endianness>::is_little_endian() {
-            true => u32::from_le_bytes(buf),
-            false => u32::from_be_bytes(buf),
+            true => decode_u32_le(buf),
+            false => decode_u32_be(buf),
         };
         Ok(val)
     }
 
-    fn read_u16<T: Endianness>(&mut self) -> io::Result<u16> {
+    fn read_u16<T: Endianness>(&mut self) -> Result<u16> {
         let mut buf = [0u8; 2];
         fill_buf(self, &mut buf)?;
         let val = match <T as Endianness>::is_little_endian() {
-            true => u16::from_le_bytes(buf),
-            false => u16::from_be_bytes(buf),
+            true => decode_u16_le(buf),
+            false => decode_u16_be(buf),
         };
         Ok(val)
     }
 
-    fn read_u8(&mut self) -> io::Result<u8> {
+    fn read_u8(&mut self) -> Result<u8> {
         let buf = &mut [0u8; 1];
         fill_buf(self, buf)?;
         Ok(buf[0])
     }
 
-    fn read_i64<T: Endianness>(&mut self) -> io::Result<i64> {
+    fn read_i128<T: Endianness>(&mut self) -> Result<i128> {
+        self.read_u128::<T>().map(|v| v as i128)
+    }
+
+    fn read_i64<T: Endianness>(&mut self) -> Result<i64> {
         self.read_u64::<T>().map(|v| v as i64)
     }
 
-    fn read_i32<T: Endianness>(&mut self) -> io::Result<i32> {
+    fn read_i32<T: Endianness>(&mut self) -> Result<i32> {
         self.read_u32::<T>().map(|v| v as i32)
     }
 
-    fn read_i16<T: Endianness>(&mut self) -> io::Result<i16> {
+    fn read_i16<T: Endianness>(&mut self) -> Result<i16> {
         self.read_u16::<T>().map(|v| v as i16)
     }
 
-    fn read_i8(&mut self) -> io::Result<i8> {
+    fn read_i8(&mut self) -> Result<i8> {
         self.read_u8().map(|v| v as i8)
     }
 
-    fn read_f64<T: Endianness>(&mut self) -> io::Result<f64> {
-        self.read_u64::<T>().map(|v| f64::from_bits(v))
+    fn read_f64<T: Endianness>(&mut self) -> Result<f64> {
+        self.read_u64::<T>().map(f64::from_bits)
+    }
+
+    fn read_f32<T: Endianness>(&mut self) -> Result<f32> {
+        self.read_u32::<T>().map(f32::from_bits)
+    }
+
+    fn read_u128_dyn(&mut self, endian: Endian) -> Result<u128> {
+        match endian {
+            Endian::Little => self.read_u128::<LittleEndian>(),
+            Endian::Big => self.read_u128::<BigEndian>(),
+        }
+    }
+
+    fn read_u64_dyn(&mut self, endian: Endian) -> Result<u64> {
+        match endian {
+            Endian::Little => self.read_u64::<LittleEndian>(),
+            Endian::Big => self.read_u64::<BigEndian>(),
+        }
+    }
+
+    fn read_u32_dyn(&mut self, endian: Endian) -> Result<u32> {
+        match endian {
+            Endian::Little => self.read_u32::<LittleEndian>(),
+            Endian::Big => self.read_u32::<BigEndian>(),
+        }
+    }
+
+    fn read_u16_dyn(&mut self, endian: Endian) -> Result<u16> {
+        match endian {
+            Endian::Little => self.read_u16::<LittleEndian>(),
+            Endian::Big => self.read_u16::<BigEndian>(),
+        }
+    }
+
+    fn read_i128_dyn(&mut self, endian: Endian) -> Result<i128> {
+        match endian {
+            Endian::Little => self.read_i128::<LittleEndian>(),
+            Endian::Big => self.read_i128::<BigEndian>(),
+        }
+    }
+
+    fn read_i64_dyn(&mut self, endian: Endian) -> Result<i64> {
+        match endian {
+            Endian::Little => self.read_i64::<LittleEndian>(),
+            Endian::Big => self.read_i64::<BigEndian>(),
+        }
+    }
+
+    fn read_i32_dyn(&mut self, endian: Endian) -> Result<i32> {
+        match endian {
+            Endian::Little => self.read_i32::<LittleEndian>(),
+            Endian::Big => self.read_i32::<BigEndian>(),
+        }
+    }
+
+    fn read_i16_dyn(&mut self, endian: Endian) -> Result<i16> {
+        match endian {
+            Endian::Little => self.read_i16::<LittleEndian>(),
+            Endian::Big => self.read_i16::<BigEndian>(),
+        }
     }
 
-    fn read_f32<T: Endianness>(&mut self) -> io::Result<f32> {
-        self.read_u32::<T>().map(|v| f32::from_bits(v))
+    fn read_f32_dyn(&mut self, endian: Endian) -> Result<f32> {
+        match endian {
+            Endian::Little => self.read_f32::<LittleEndian>(),
+            Endian::Big => self.read_f32::<BigEndian>(),
+        }
     }
 
-    fn read_exact(&mut self, len: usize) -> io::Result<Vec<u8>> {
-        let mut res = vec![0; len];
-        fill_buf(self, &mut res)?;
+    fn read_f64_dyn(&mut self, endian: Endian) -> Result<f64> {
+        match endian {
+            Endian::Little => self.read_f64::<LittleEndian>(),
+            Endian::Big => self.read_f64::<BigEndian>(),
+        }
+    }
+
+    fn read_uleb128(&mut self) -> Result<u64> {
+        varint::read_uleb128(self, false)
+    }
+
+    fn read_uleb128_strict(&mut self) -> Result<u64> {
+        varint::read_uleb128(self, true)
+    }
+
+    fn read_sleb128(&mut self) -> Result<i64> {
+        varint::read_sleb128(self, false)
+    }
+
+    fn read_sleb128_strict(&mut self) -> Result<i64> {
+        varint::read_sleb128(self, true)
+    }
+
+    fn read_zigzag_i32(&mut self) -> Result<i32> {
+        varint::read_zigzag_i32(self)
+    }
+
+    fn read_zigzag_i64(&mut self) -> Result<i64> {
+        varint::read_zigzag_i64(self)
+    }
+
+    fn read_quic_varint(&mut self) -> Result<u64> {
+        varint::read_quic_varint(self, false)
+    }
+
+    fn read_quic_varint_strict(&mut self) -> Result<u64> {
+        varint::read_quic_varint(self, true)
+    }
+
+    fn read_u128_into<T: Endianness>(&mut self, dst: &mut [u128]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_u64_into<T: Endianness>(&mut self, dst: &mut [u64]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_u32_into<T: Endianness>(&mut self, dst: &mut [u32]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_u16_into<T: Endianness>(&mut self, dst: &mut [u16]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_i128_into<T: Endianness>(&mut self, dst: &mut [i128]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_i64_into<T: Endianness>(&mut self, dst: &mut [i64]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_i32_into<T: Endianness>(&mut self, dst: &mut [i32]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_i16_into<T: Endianness>(&mut self, dst: &mut [i16]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = v.swap_bytes();
+            }
+        }
+        Ok(())
+    }
+
+    fn read_f32_into<T: Endianness>(&mut self, dst: &mut [f32]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = f32::from_bits(v.to_bits().swap_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn read_f64_into<T: Endianness>(&mut self, dst: &mut [f64]) -> Result<()> {
+        fill_buf(self, unsafe { slice_as_bytes_mut(dst) })?;
+        if needs_swap::<T>() {
+            for v in dst.iter_mut() {
+                *v = f64::from_bits(v.to_bits().swap_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn read_exact(&mut self, len: usize) -> Result<Vec<u8>> {
+        // Grow the buffer as data arrives, so a bogus length cannot exhaust memory up front
+        let mut res = Vec::new();
+        while res.len() < len {
+            let start = res.len();
+            let end = start + cmp::min(len - start, cmp::max(start, READ_CHUNK_SIZE));
+            res.resize(end, 0);
+            let read = read_full(self, &mut res[start..])?;
+            if read != end - start {
+                return Err(unexpected_eof(start + read, len));
+            }
+        }
         Ok(res)
     }
+
+    fn read_exact_limited(&mut self, len: usize, limit: usize) -> Result<Vec<u8>> {
+        if len > limit {
+            return Err(limit_exceeded(len as u64, limit));
+        }
+        ReadPodExt::read_exact(self, len)
+    }
+
+    fn read_prefixed_bytes<P: LengthPrefix, E: Endianness>(&mut self, max_len: usize) -> Result<Vec<u8>> {
+        let len = P::read_len::<Self, E>(self)?;
+        if len > max_len as u64 {
+            return Err(limit_exceeded(len, max_len));
+        }
+        ReadPodExt::read_exact(self, len as usize)
+    }
+
+    fn read_cstring(&mut self, max_len: usize) -> Result<CString> {
+        string::read_cstring(self, max_len)
+    }
+
+    fn read_fixed_str(&mut self, width: usize, pad: u8) -> Result<String> {
+        string::read_fixed_str(self, width, pad)
+    }
+
+    fn read_utf16<T: Endianness>(&mut self, len: usize) -> Result<String> {
+        string::read_utf16_units::<Self, T>(self, len).and_then(|units| string::decode_utf16(&units))
+    }
+
+    fn read_utf16_lossy<T: Endianness>(&mut self, len: usize) -> Result<String> {
+        string::read_utf16_units::<Self, T>(self, len).map(|units| String::from_utf16_lossy(&units))
+    }
+
+    fn read_utf16_nul<T: Endianness>(&mut self, max_len: usize) -> Result<String> {
+        string::read_utf16_nul_units::<Self, T>(self, max_len).and_then(|units| string::decode_utf16(&units))
+    }
+
+    fn read_utf16_nul_lossy<T: Endianness>(&mut self, max_len: usize) -> Result<String> {
+        string::read_utf16_nul_units::<Self, T>(self, max_len).map(|units| String::from_utf16_lossy(&units))
+    }
+
+    fn read_pod<P: ReadPod, T: Endianness>(&mut self) -> Result<P> {
+        P::read_from::<Self, T>(self)
+    }
 }
@@ -0,0 +1,120 @@
+//! Type-driven reading and writing
+
+use core::array;
+
+use {Endianness, PodRead, PodWrite, ReadPodExt, Result, WritePodExt};
+
+/// A value that can be read from a reader in a given endianness
+///
+/// Implemented for all primitive integers and floats, and for arrays and tuples (up to twelve
+/// elements) of those, each element being read in the same endianness. Use
+/// `ReadPodExt::read_pod` to read one.
+pub trait ReadPod: Sized {
+    /// Read a value
+    fn read_from<R: PodRead, E: Endianness>(reader: &mut R) -> Result<Self>;
+}
+
+/// A value that can be written to a writer in a given endianness
+///
+/// Implemented for the same types as `ReadPod`. Use `WritePodExt::write_pod` to write one.
+pub trait WritePod {
+    /// Write the value
+    fn write_to<W: PodWrite, E: Endianness>(&self, writer: &mut W) -> Result<()>;
+}
+
+macro_rules! impl_pod {
+    ($ty:ty, $read:ident, $write:ident) => {
+        impl ReadPod for $ty {
+            fn read_from<R: PodRead, E: Endianness>(reader: &mut R) -> Result<$ty> {
+                reader.$read::<E>()
+            }
+        }
+
+        impl WritePod for $ty {
+            fn write_to<W: PodWrite, E: Endianness>(&self, writer: &mut W) -> Result<()> {
+                writer.$write::<E>(*self)
+            }
+        }
+    };
+    ($ty:ty, $read:ident, $write:ident, single byte) => {
+        impl ReadPod for $ty {
+            fn read_from<R: PodRead, E: Endianness>(reader: &mut R) -> Result<$ty> {
+                reader.$read()
+            }
+        }
+
+        impl WritePod for $ty {
+            fn write_to<W: PodWrite, E: Endianness>(&self, writer: &mut W) -> Result<()> {
+                writer.$write(*self)
+            }
+        }
+    };
+}
+
+impl_pod!(u8, read_u8, write_u8, single byte);
+impl_pod!(i8, read_i8, write_i8, single byte);
+impl_pod!(u16, read_u16, write_u16);
+impl_pod!(i16, read_i16, write_i16);
+impl_pod!(u32, read_u32, write_u32);
+impl_pod!(i32, read_i32, write_i32);
+impl_pod!(u64, read_u64, write_u64);
+impl_pod!(i64, read_i64, write_i64);
+impl_pod!(u128, read_u128, write_u128);
+impl_pod!(i128, read_i128, write_i128);
+impl_pod!(f32, read_f32, write_f32);
+impl_pod!(f64, read_f64, write_f64);
+
+impl<T: ReadPod, const N: usize> ReadPod for [T; N] {
+    fn read_from<R: PodRead, E: Endianness>(reader: &mut R) -> Result<[T; N]> {
+        let mut err = None;
+        let vals: [Option<T>; N] = array::from_fn(|_| {
+            if err.is_some() {
+                return None;
+            }
+            T::read_from::<R, E>(reader).map_err(|e| err = Some(e)).ok()
+        });
+        match err {
+            Some(e) => Err(e),
+            None => Ok(vals.map(|v| v.expect("all elements were read"))),
+        }
+    }
+}
+
+impl<T: WritePod, const N: usize> WritePod for [T; N] {
+    fn write_to<W: PodWrite, E: Endianness>(&self, writer: &mut W) -> Result<()> {
+        for val in self {
+            val.write_to::<W, E>(writer)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_pod_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: ReadPod),+> ReadPod for ($($name,)+) {
+            fn read_from<R: PodRead, E: Endianness>(reader: &mut R) -> Result<($($name,)+)> {
+                Ok(($($name::read_from::<R, E>(reader)?,)+))
+            }
+        }
+
+        impl<$($name: WritePod),+> WritePod for ($($name,)+) {
+            fn write_to<W: PodWrite, E: Endianness>(&self, writer: &mut W) -> Result<()> {
+                $(self.$idx.write_to::<W, E>(writer)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_pod_tuple!(A 0);
+impl_pod_tuple!(A 0, B 1);
+impl_pod_tuple!(A 0, B 1, C 2);
+impl_pod_tuple!(A 0, B 1, C 2, D 3);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6, I 7);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6, I 7, J 8);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6, I 7, J 8, K 9);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6, I 7, J 8, K 9, L 10);
+impl_pod_tuple!(A 0, B 1, C 2, D 3, F 4, G 5, H 6, I 7, J 8, K 9, L 10, M 11);
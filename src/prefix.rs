@@ -0,0 +1,79 @@
+//! Length prefixes for byte strings
+
+use {Endianness, Error, ErrorKind, PodRead, PodWrite, ReadPodExt, Result, WritePodExt};
+
+/// Type of the length field in front of a length-prefixed byte string
+///
+/// Implemented for `u8`, `u16`, `u32`, `u64` and `Uleb128`.
+pub trait LengthPrefix {
+    /// Read a length field
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64>;
+    /// Write a length field, failing with `ErrorKind::InvalidInput` if it does not fit
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()>;
+}
+
+/// Unsigned LEB128 length prefix, as used by WebAssembly and Protocol Buffers
+pub enum Uleb128 {}
+
+fn too_long() -> Error {
+    Error::new(ErrorKind::InvalidInput, "Length does not fit in the prefix")
+}
+
+impl LengthPrefix for u8 {
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64> {
+        reader.read_u8().map(u64::from)
+    }
+
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()> {
+        if len > u8::MAX as usize {
+            return Err(too_long());
+        }
+        writer.write_u8(len as u8)
+    }
+}
+
+impl LengthPrefix for u16 {
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64> {
+        reader.read_u16::<E>().map(u64::from)
+    }
+
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()> {
+        if len > u16::MAX as usize {
+            return Err(too_long());
+        }
+        writer.write_u16::<E>(len as u16)
+    }
+}
+
+impl LengthPrefix for u32 {
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64> {
+        reader.read_u32::<E>().map(u64::from)
+    }
+
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()> {
+        if len as u64 > u64::from(u32::MAX) {
+            return Err(too_long());
+        }
+        writer.write_u32::<E>(len as u32)
+    }
+}
+
+impl LengthPrefix for u64 {
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64> {
+        reader.read_u64::<E>()
+    }
+
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()> {
+        writer.write_u64::<E>(len as u64)
+    }
+}
+
+impl LengthPrefix for Uleb128 {
+    fn read_len<R: PodRead, E: Endianness>(reader: &mut R) -> Result<u64> {
+        reader.read_uleb128()
+    }
+
+    fn write_len<W: PodWrite, E: Endianness>(writer: &mut W, len: usize) -> Result<()> {
+        writer.write_uleb128(len as u64)
+    }
+}
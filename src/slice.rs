@@ -0,0 +1,420 @@
+//! Bounds-checked access to in-memory buffers
+
+use core::cmp;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{error, io};
+
+use {Endianness, Error, UnexpectedEof};
+use {decode_i128_be, decode_i128_le, decode_i16_be, decode_i16_le, decode_i32_be, decode_i32_le,
+     decode_i64_be, decode_i64_le, decode_u128_be, decode_u128_le, decode_u16_be, decode_u16_le,
+     decode_u32_be, decode_u32_le, decode_u64_be, decode_u64_le};
+#[cfg(not(feature = "std"))]
+use {PodRead, PodWrite};
+
+/// Error returned by a `SliceReader` when a read runs past the end of its buffer
+///
+/// This is a small `Copy` value rather than an `io::Error`, so failing a read costs nothing.
+/// It converts into an `UnexpectedEof` error for use with `?` in `io::Result` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    offset: usize,
+    requested: usize,
+    available: usize,
+}
+
+impl OutOfBounds {
+    /// Offset in the buffer at which the failing read started
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of bytes the read needed
+    pub fn requested(&self) -> usize {
+        self.requested
+    }
+
+    /// Number of bytes that were left in the buffer
+    pub fn available(&self) -> usize {
+        self.available
+    }
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Read of {} bytes at offset {} is out of bounds: {} bytes left",
+               self.requested, self.offset, self.available)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for OutOfBounds {}
+
+impl From<OutOfBounds> for Error {
+    fn from(err: OutOfBounds) -> Error {
+        UnexpectedEof { read: err.available, expected: err.requested }.into()
+    }
+}
+
+/// A cursor over a byte slice, for reading values that are already in memory
+///
+/// Reads never allocate or copy more than the value itself: a failed read returns an
+/// `OutOfBounds` and leaves the position unchanged, and `read_bytes` borrows from the buffer.
+///
+/// ```
+/// use podio::{BigEndian, LittleEndian, SliceReader};
+///
+/// let mut reader = SliceReader::new(&[0x01, 0x23, 0x45, 0x67, 0x02, 0x00, b'h', b'i']);
+/// assert_eq!(reader.read_u32::<BigEndian>(), Ok(0x01234567));
+/// let len = reader.read_u16::<LittleEndian>().unwrap();
+/// assert_eq!(reader.read_bytes(len as usize), Ok(&b"hi"[..]));
+///
+/// let err = reader.read_u8().unwrap_err();
+/// assert_eq!(err.offset(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SliceReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+macro_rules! slice_read {
+    ($($name:ident -> $ty:ident, $dec_le:ident, $dec_be:ident;)*) => {$(
+        #[doc = concat!("Read a ", stringify!($ty))]
+        pub fn $name<T: Endianness>(&mut self) -> Result<$ty, OutOfBounds> {
+            let buf = self.read_array()?;
+            let val = match <T as Endianness>::is_little_endian() {
+                true => $dec_le(buf),
+                false => $dec_be(buf),
+            };
+            Ok(val)
+        }
+    )*};
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a reader positioned at the start of `buf`
+    pub fn new(buf: &'a [u8]) -> SliceReader<'a> {
+        SliceReader { buf, pos: 0 }
+    }
+
+    /// Current offset in the buffer
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bytes left to read
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Whether all bytes have been read
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bytes left to read
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Borrow the next `len` bytes from the buffer
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], OutOfBounds> {
+        if len > self.len() {
+            return Err(OutOfBounds { offset: self.pos, requested: len, available: self.len() });
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Skip over the next `len` bytes
+    pub fn skip(&mut self, len: usize) -> Result<(), OutOfBounds> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], OutOfBounds> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.read_bytes(N)?);
+        Ok(buf)
+    }
+
+    slice_read! {
+        read_u128 -> u128, decode_u128_le, decode_u128_be;
+        read_u64 -> u64, decode_u64_le, decode_u64_be;
+        read_u32 -> u32, decode_u32_le, decode_u32_be;
+        read_u16 -> u16, decode_u16_le, decode_u16_be;
+        read_i128 -> i128, decode_i128_le, decode_i128_be;
+        read_i64 -> i64, decode_i64_le, decode_i64_be;
+        read_i32 -> i32, decode_i32_le, decode_i32_be;
+        read_i16 -> i16, decode_i16_le, decode_i16_be;
+    }
+
+    /// Read a u8
+    pub fn read_u8(&mut self) -> Result<u8, OutOfBounds> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    /// Read a i8
+    pub fn read_i8(&mut self) -> Result<i8, OutOfBounds> {
+        self.read_u8().map(|val| val as i8)
+    }
+
+    /// Read a f32
+    pub fn read_f32<T: Endianness>(&mut self) -> Result<f32, OutOfBounds> {
+        self.read_u32::<T>().map(f32::from_bits)
+    }
+
+    /// Read a f64
+    pub fn read_f64<T: Endianness>(&mut self) -> Result<f64, OutOfBounds> {
+        self.read_u64::<T>().map(f64::from_bits)
+    }
+
+    fn read_some(&mut self, buf: &mut [u8]) -> usize {
+        let len = cmp::min(buf.len(), self.len());
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        len
+    }
+}
+
+// Lets the `ReadPodExt` methods that have no counterpart here, such as varints, read from a
+// `SliceReader` too
+#[cfg(feature = "std")]
+impl io::Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_some(buf))
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl PodRead for SliceReader<'_> {
+    fn pod_read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        Ok(self.read_some(buf))
+    }
+}
+
+/// Error payload for an `io::ErrorKind::WriteZero` raised when a `SliceWriter` or `ArrayWriter`
+/// runs out of capacity
+///
+/// ```
+/// use podio::{BigEndian, CapacityExceeded, SliceWriter, WritePodExt};
+///
+/// let mut buf = [0u8; 6];
+/// let mut writer = SliceWriter::new(&mut buf);
+/// writer.write_u32::<BigEndian>(1).unwrap();
+/// let err = writer.write_u32::<BigEndian>(2).unwrap_err();
+///
+/// let full = err.get_ref().and_then(|e| e.downcast_ref::<CapacityExceeded>()).unwrap();
+/// assert_eq!(full.offset(), 4);
+/// assert_eq!(full.requested(), 4);
+/// assert_eq!(full.available(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityExceeded {
+    offset: usize,
+    requested: usize,
+    available: usize,
+}
+
+impl CapacityExceeded {
+    /// Offset in the buffer at which the failing write started
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of bytes the write needed
+    pub fn requested(&self) -> usize {
+        self.requested
+    }
+
+    /// Number of bytes that were left in the buffer
+    pub fn available(&self) -> usize {
+        self.available
+    }
+}
+
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Write of {} bytes at offset {} exceeds the capacity: {} bytes left",
+               self.requested, self.offset, self.available)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for CapacityExceeded {}
+
+/// Copies all of `bytes` into `buf` at `*pos`, or nothing if they do not fit
+fn write_at(buf: &mut [u8], pos: &mut usize, bytes: &[u8]) -> Result<(), CapacityExceeded> {
+    let available = buf.len() - *pos;
+    if bytes.len() > available {
+        return Err(CapacityExceeded { offset: *pos, requested: bytes.len(), available });
+    }
+    buf[*pos..*pos + bytes.len()].copy_from_slice(bytes);
+    *pos += bytes.len();
+    Ok(())
+}
+
+/// A writer into a borrowed byte slice, for encoding without allocating
+///
+/// The `WritePodExt` methods write into the slice, and fail with a `CapacityExceeded` payload
+/// when a value does not fit. A single write call that does not fit writes nothing, so a
+/// fixed-width value is either written whole or not at all. Methods that make several write
+/// calls, such as `write_prefixed_bytes`, `write_utf16`, the `write_*_slice` methods and
+/// `write_pod` of compound values, keep the parts that fit written, and `position()` moves past
+/// them.
+///
+/// ```
+/// use podio::{LittleEndian, SliceWriter, WritePodExt};
+///
+/// let mut buf = [0u8; 16];
+/// let mut writer = SliceWriter::new(&mut buf);
+/// writer.write_u16::<LittleEndian>(0x0123).unwrap();
+/// writer.write_uleb128(300).unwrap();
+/// assert_eq!(writer.remaining(), 12);
+/// assert_eq!(writer.into_written(), &[0x23, 0x01, 0xac, 0x02]);
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a writer positioned at the start of `buf`
+    pub fn new(buf: &'a mut [u8]) -> SliceWriter<'a> {
+        SliceWriter { buf, pos: 0 }
+    }
+
+    /// Current offset in the buffer, which is also the number of bytes written
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Total size of the buffer
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Number of bytes that can still be written
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Whether the buffer is full
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The bytes written so far
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    /// Consumes the writer, returning the bytes written
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.pos]
+    }
+
+    /// Write all of `bytes`, or nothing if they do not fit
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), CapacityExceeded> {
+        write_at(self.buf, &mut self.pos, bytes)
+    }
+}
+
+/// A writer into an owned, fixed-size array, for encoding on the stack
+///
+/// This behaves like a `SliceWriter` that owns its buffer.
+///
+/// ```
+/// use podio::{ArrayWriter, BigEndian, WritePodExt};
+///
+/// let mut writer = ArrayWriter::<4>::new();
+/// writer.write_u16::<BigEndian>(0x0123).unwrap();
+/// assert!(writer.write_u32::<BigEndian>(0).is_err());
+/// assert_eq!(writer.written(), &[0x01, 0x23]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArrayWriter<const N: usize> {
+    buf: [u8; N],
+    pos: usize,
+}
+
+impl<const N: usize> ArrayWriter<N> {
+    /// Creates an empty writer
+    pub fn new() -> ArrayWriter<N> {
+        ArrayWriter { buf: [0; N], pos: 0 }
+    }
+
+    /// Current offset in the buffer, which is also the number of bytes written
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Total size of the buffer
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Number of bytes that can still be written
+    pub fn remaining(&self) -> usize {
+        N - self.pos
+    }
+
+    /// Whether the buffer is full
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The bytes written so far
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    /// Consumes the writer, returning the whole array and the number of bytes written
+    pub fn into_inner(self) -> ([u8; N], usize) {
+        (self.buf, self.pos)
+    }
+
+    /// Write all of `bytes`, or nothing if they do not fit
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), CapacityExceeded> {
+        write_at(&mut self.buf, &mut self.pos, bytes)
+    }
+}
+
+impl<const N: usize> Default for ArrayWriter<N> {
+    fn default() -> ArrayWriter<N> {
+        ArrayWriter::new()
+    }
+}
+
+macro_rules! impl_write {
+    ($ty:ty, $($gen:tt)*) => {
+        #[cfg(feature = "std")]
+        impl<$($gen)*> io::Write for $ty {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                // Failing instead of writing a prefix keeps the precise error from `write_all`
+                self.write_bytes(buf)?;
+                Ok(buf.len())
+            }
+
+            fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+                Ok(self.write_bytes(buf)?)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl<$($gen)*> PodWrite for $ty {
+            fn pod_write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+                Ok(self.write_bytes(buf)?)
+            }
+        }
+    };
+}
+
+impl_write!(SliceWriter<'_>, );
+impl_write!(ArrayWriter<N>, const N: usize);
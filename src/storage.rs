@@ -0,0 +1,159 @@
+//! Endian-tagged storage types for `#[repr(C)]` overlays
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use Endianness;
+use {decode_f32_be, decode_f32_le, decode_f64_be, decode_f64_le, decode_i128_be, decode_i128_le,
+     decode_i16_be, decode_i16_le, decode_i32_be, decode_i32_le, decode_i64_be, decode_i64_le,
+     decode_u128_be, decode_u128_le, decode_u16_be, decode_u16_le, decode_u32_be, decode_u32_le,
+     decode_u64_be, decode_u64_le};
+use {encode_f32_be, encode_f32_le, encode_f64_be, encode_f64_le, encode_i128_be, encode_i128_le,
+     encode_i16_be, encode_i16_le, encode_i32_be, encode_i32_le, encode_i64_be, encode_i64_le,
+     encode_u128_be, encode_u128_le, encode_u16_be, encode_u16_le, encode_u32_be, encode_u32_le,
+     encode_u64_be, encode_u64_le};
+
+macro_rules! storage {
+    ($name:ident, $ty:ident, $n:expr, $enc_le:ident, $enc_be:ident, $dec_le:ident, $dec_be:ident) => {
+        #[doc = concat!("A ", stringify!($ty), " stored as bytes in the endianness `E`")]
+        ///
+        /// It has the size of the value and an alignment of 1, so it can be placed anywhere in a
+        /// `#[repr(C)]` struct that overlays a byte buffer. Comparisons use the logical value.
+        #[repr(transparent)]
+        pub struct $name<E: Endianness> {
+            bytes: [u8; $n],
+            endian: PhantomData<E>,
+        }
+
+        impl<E: Endianness> $name<E> {
+            /// Creates the stored form of `val`
+            pub fn new(val: $ty) -> $name<E> {
+                $name::from_bytes(match E::is_little_endian() {
+                    true => $enc_le(val),
+                    false => $enc_be(val),
+                })
+            }
+
+            /// Wraps bytes that are already in the endianness `E`
+            pub const fn from_bytes(bytes: [u8; $n]) -> $name<E> {
+                $name { bytes, endian: PhantomData }
+            }
+
+            /// The stored bytes
+            pub const fn to_bytes(self) -> [u8; $n] {
+                self.bytes
+            }
+
+            /// The logical value
+            pub fn get(self) -> $ty {
+                match E::is_little_endian() {
+                    true => $dec_le(self.bytes),
+                    false => $dec_be(self.bytes),
+                }
+            }
+
+            /// Replaces the stored value with `val`
+            pub fn set(&mut self, val: $ty) {
+                *self = $name::new(val);
+            }
+        }
+
+        impl<E: Endianness> Clone for $name<E> {
+            fn clone(&self) -> $name<E> {
+                *self
+            }
+        }
+
+        impl<E: Endianness> Copy for $name<E> {}
+
+        impl<E: Endianness> Default for $name<E> {
+            fn default() -> $name<E> {
+                $name::from_bytes([0; $n])
+            }
+        }
+
+        impl<E: Endianness> fmt::Debug for $name<E> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        impl<E: Endianness> PartialEq for $name<E> {
+            fn eq(&self, other: &$name<E>) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        impl<E: Endianness> From<$ty> for $name<E> {
+            fn from(val: $ty) -> $name<E> {
+                $name::new(val)
+            }
+        }
+
+        impl<E: Endianness> From<$name<E>> for $ty {
+            fn from(val: $name<E>) -> $ty {
+                val.get()
+            }
+        }
+    };
+}
+
+// Floats have no total order, so only integers get `Eq`, `Ord` and `Hash`
+macro_rules! storage_ord {
+    ($name:ident) => {
+        impl<E: Endianness> PartialOrd for $name<E> {
+            fn partial_cmp(&self, other: &$name<E>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<E: Endianness> Eq for $name<E> {}
+
+        impl<E: Endianness> Ord for $name<E> {
+            fn cmp(&self, other: &$name<E>) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<E: Endianness> Hash for $name<E> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.get().hash(state);
+            }
+        }
+    };
+}
+
+macro_rules! storage_partial_ord {
+    ($name:ident) => {
+        impl<E: Endianness> PartialOrd for $name<E> {
+            fn partial_cmp(&self, other: &$name<E>) -> Option<Ordering> {
+                self.get().partial_cmp(&other.get())
+            }
+        }
+    };
+}
+
+storage!(U16, u16, 2, encode_u16_le, encode_u16_be, decode_u16_le, decode_u16_be);
+storage!(U32, u32, 4, encode_u32_le, encode_u32_be, decode_u32_le, decode_u32_be);
+storage!(U64, u64, 8, encode_u64_le, encode_u64_be, decode_u64_le, decode_u64_be);
+storage!(U128, u128, 16, encode_u128_le, encode_u128_be, decode_u128_le, decode_u128_be);
+storage!(I16, i16, 2, encode_i16_le, encode_i16_be, decode_i16_le, decode_i16_be);
+storage!(I32, i32, 4, encode_i32_le, encode_i32_be, decode_i32_le, decode_i32_be);
+storage!(I64, i64, 8, encode_i64_le, encode_i64_be, decode_i64_le, decode_i64_be);
+storage!(I128, i128, 16, encode_i128_le, encode_i128_be, decode_i128_le, decode_i128_be);
+storage!(F32, f32, 4, encode_f32_le, encode_f32_be, decode_f32_le, decode_f32_be);
+storage!(F64, f64, 8, encode_f64_le, encode_f64_be, decode_f64_le, decode_f64_be);
+
+storage_ord!(U16);
+storage_ord!(U32);
+storage_ord!(U64);
+storage_ord!(U128);
+storage_ord!(I16);
+storage_ord!(I32);
+storage_ord!(I64);
+storage_ord!(I128);
+
+storage_partial_ord!(F32);
+storage_partial_ord!(F64);
@@ -0,0 +1,91 @@
+//! String fields
+
+use alloc::ffi::CString;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ffi::CStr;
+
+use {Endianness, Error, ErrorKind, PodRead, PodWrite, ReadPodExt, Result, UnexpectedEof, WritePodExt};
+use {invalid_data, limit_exceeded, unexpected_eof};
+
+/// Extends the `UnexpectedEof` of the unit read at byte `offset` to cover the whole string
+fn eof_before_terminator(e: Error, offset: usize) -> Error {
+    match e.get_ref().and_then(|inner| inner.downcast_ref::<UnexpectedEof>()) {
+        Some(eof) => unexpected_eof(offset + eof.bytes_read(), offset + eof.bytes_expected()),
+        None => e,
+    }
+}
+
+pub fn read_cstring<R: PodRead>(reader: &mut R, max_len: usize) -> Result<CString> {
+    let mut buf = Vec::new();
+    loop {
+        let byte = reader.read_u8().map_err(|e| eof_before_terminator(e, buf.len()))?;
+        if byte == 0 {
+            break;
+        }
+        if buf.len() == max_len {
+            return Err(limit_exceeded(max_len as u64 + 1, max_len));
+        }
+        buf.push(byte);
+    }
+    CString::new(buf).map_err(|_| invalid_data("NUL byte inside a C string"))
+}
+
+pub fn write_cstring<W: PodWrite>(writer: &mut W, val: &CStr) -> Result<()> {
+    writer.pod_write_all(val.to_bytes_with_nul())
+}
+
+pub fn read_fixed_str<R: PodRead>(reader: &mut R, width: usize, pad: u8) -> Result<String> {
+    let mut buf = ReadPodExt::read_exact(reader, width)?;
+    let len = buf.iter().rposition(|&b| b != pad).map_or(0, |i| i + 1);
+    buf.truncate(len);
+    String::from_utf8(buf).map_err(|_| invalid_data("String field is not valid UTF-8"))
+}
+
+pub fn write_fixed_str<W: PodWrite>(writer: &mut W, val: &str, width: usize, pad: u8) -> Result<()> {
+    if val.len() > width {
+        return Err(Error::new(ErrorKind::InvalidInput, "String does not fit in the field"));
+    }
+    let mut buf = Vec::with_capacity(width);
+    buf.extend_from_slice(val.as_bytes());
+    buf.resize(width, pad);
+    writer.pod_write_all(&buf)
+}
+
+pub fn read_utf16_units<R: PodRead, E: Endianness>(reader: &mut R, len: usize) -> Result<Vec<u16>> {
+    let mut units = Vec::new();
+    for _ in 0..len {
+        units.push(reader.read_u16::<E>()?);
+    }
+    Ok(units)
+}
+
+pub fn read_utf16_nul_units<R: PodRead, E: Endianness>(reader: &mut R, max_len: usize) -> Result<Vec<u16>> {
+    let mut units = Vec::new();
+    loop {
+        let unit = reader.read_u16::<E>().map_err(|e| eof_before_terminator(e, 2 * units.len()))?;
+        if unit == 0 {
+            return Ok(units);
+        }
+        if units.len() == max_len {
+            return Err(limit_exceeded(max_len as u64 + 1, max_len));
+        }
+        units.push(unit);
+    }
+}
+
+pub fn decode_utf16(units: &[u16]) -> Result<String> {
+    String::from_utf16(units).map_err(|_| invalid_data("Unpaired surrogate in UTF-16 string"))
+}
+
+pub fn write_utf16<W: PodWrite, E: Endianness>(writer: &mut W, val: &str, nul: bool) -> Result<()> {
+    // An interior NUL would end the string early when read back
+    if nul && val.contains('\0') {
+        return Err(Error::new(ErrorKind::InvalidInput, "String contains a NUL character"));
+    }
+    let mut units: Vec<u16> = val.encode_utf16().collect();
+    if nul {
+        units.push(0);
+    }
+    writer.write_u16_slice::<E>(&units)
+}
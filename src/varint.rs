@@ -0,0 +1,143 @@
+//! Variable-length integer encodings
+
+use {BigEndian, Error, ErrorKind, PodRead, PodWrite, ReadPodExt, Result, WritePodExt, fill_buf, invalid_data};
+
+/// Maximum encoded length of a 64-bit LEB128 value
+const LEB128_MAX_LEN: usize = 10;
+
+pub fn read_uleb128<R: PodRead>(reader: &mut R, strict: bool) -> Result<u64> {
+    let mut result = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = reader.read_u8()?;
+        // The tenth byte may only carry bit 63
+        if shift == 63 && byte > 0x01 {
+            return Err(invalid_data("LEB128 value does not fit in a u64"));
+        }
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            if strict && shift != 0 && byte == 0 {
+                return Err(invalid_data("Overlong LEB128 encoding"));
+            }
+            return Ok(result);
+        }
+        shift += 7;
+    }
+}
+
+pub fn read_sleb128<R: PodRead>(reader: &mut R, strict: bool) -> Result<i64> {
+    let mut result = 0i64;
+    let mut shift = 0;
+    let mut prev = 0u8;
+    loop {
+        let byte = reader.read_u8()?;
+        // The tenth byte carries bit 63, the rest must be its sign extension
+        if shift == 63 && byte != 0x00 && byte != 0x7f {
+            return Err(invalid_data("LEB128 value does not fit in an i64"));
+        }
+        result |= i64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if strict && shift > 7
+                && ((byte == 0x00 && prev & 0x40 == 0) || (byte == 0x7f && prev & 0x40 != 0)) {
+                return Err(invalid_data("Overlong LEB128 encoding"));
+            }
+            if shift < 64 && byte & 0x40 != 0 {
+                result |= -1 << shift;
+            }
+            return Ok(result);
+        }
+        prev = byte;
+    }
+}
+
+pub fn write_uleb128<W: PodWrite>(writer: &mut W, mut val: u64) -> Result<()> {
+    let mut buf = [0u8; LEB128_MAX_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        if val == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.pod_write_all(&buf[..len])
+}
+
+pub fn write_sleb128<W: PodWrite>(writer: &mut W, mut val: i64) -> Result<()> {
+    let mut buf = [0u8; LEB128_MAX_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        let done = (val == 0 && byte & 0x40 == 0) || (val == -1 && byte & 0x40 != 0);
+        if done {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.pod_write_all(&buf[..len])
+}
+
+pub fn read_zigzag_i32<R: PodRead>(reader: &mut R) -> Result<i32> {
+    let raw = read_uleb128(reader, false)?;
+    if raw > u64::from(u32::MAX) {
+        return Err(invalid_data("Zigzag varint does not fit in an i32"));
+    }
+    let raw = raw as u32;
+    Ok((raw >> 1) as i32 ^ -((raw & 1) as i32))
+}
+
+pub fn read_zigzag_i64<R: PodRead>(reader: &mut R) -> Result<i64> {
+    let raw = read_uleb128(reader, false)?;
+    Ok((raw >> 1) as i64 ^ -((raw & 1) as i64))
+}
+
+pub fn write_zigzag_i32<W: PodWrite>(writer: &mut W, val: i32) -> Result<()> {
+    write_uleb128(writer, u64::from(((val << 1) ^ (val >> 31)) as u32))
+}
+
+pub fn write_zigzag_i64<W: PodWrite>(writer: &mut W, val: i64) -> Result<()> {
+    write_uleb128(writer, ((val << 1) ^ (val >> 63)) as u64)
+}
+
+/// Number of bytes needed to encode a QUIC variable-length integer
+fn quic_varint_len(val: u64) -> Option<usize> {
+    match val {
+        0..=0x3f => Some(1),
+        0x40..=0x3fff => Some(2),
+        0x4000..=0x3fff_ffff => Some(4),
+        0x4000_0000..=0x3fff_ffff_ffff_ffff => Some(8),
+        _ => None,
+    }
+}
+
+pub fn read_quic_varint<R: PodRead>(reader: &mut R, strict: bool) -> Result<u64> {
+    let first = reader.read_u8()?;
+    let len = 1 << (first >> 6);
+    let mut buf = [0u8; 8];
+    buf[0] = first & 0x3f;
+    fill_buf(reader, &mut buf[1..len])?;
+    let val = buf[..len].iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+    if strict && quic_varint_len(val) != Some(len) {
+        return Err(invalid_data("Non-minimal QUIC varint encoding"));
+    }
+    Ok(val)
+}
+
+pub fn write_quic_varint<W: PodWrite>(writer: &mut W, val: u64) -> Result<()> {
+    match quic_varint_len(val) {
+        Some(1) => writer.write_u8(val as u8),
+        Some(2) => writer.write_u16::<BigEndian>(0x4000 | val as u16),
+        Some(4) => writer.write_u32::<BigEndian>(0x8000_0000 | val as u32),
+        Some(_) => writer.write_u64::<BigEndian>(0xc000_0000_0000_0000 | val),
+        None => Err(Error::new(ErrorKind::InvalidInput, "Value too large for a QUIC varint")),
+    }
+}
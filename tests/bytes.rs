@@ -1,14 +1,21 @@
+#![cfg(feature = "std")]
+
 extern crate podio;
 
 use std::io;
-use podio::{LittleEndian, BigEndian};
+use podio::{LittleEndian, BigEndian, NativeEndian, NetworkEndian, Endian};
 use podio::{ReadPodExt, WritePodExt};
 
 #[test]
 fn write_be() {
-    let buf: &mut [u8] = &mut [0u8; 8];
+    let buf: &mut [u8] = &mut [0u8; 16];
     let mut writer = io::Cursor::new(buf);
 
+    writer.set_position(0);
+    writer.write_u128::<BigEndian>(0x01_23_45_67_89_ab_cd_ef_fe_dc_ba_98_76_54_32_10).unwrap();
+    assert_eq!(&writer.get_ref()[0..16], &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                                           0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10]);
+
     writer.set_position(0);
     writer.write_u64::<BigEndian>(0x01_23_45_67_89_ab_cd_ef).unwrap();
     assert_eq!(&writer.get_ref()[0..8], &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
@@ -24,9 +31,14 @@ fn write_be() {
 
 #[test]
 fn write_le() {
-    let buf: &mut [u8] = &mut [0u8; 8];
+    let buf: &mut [u8] = &mut [0u8; 16];
     let mut writer = io::Cursor::new(buf);
 
+    writer.set_position(0);
+    writer.write_u128::<LittleEndian>(0x01_23_45_67_89_ab_cd_ef_fe_dc_ba_98_76_54_32_10).unwrap();
+    assert_eq!(&writer.get_ref()[0..16], &[0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe,
+                                           0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01]);
+
     writer.set_position(0);
     writer.write_u64::<LittleEndian>(0x01_23_45_67_89_ab_cd_ef).unwrap();
     assert_eq!(&writer.get_ref()[0..8], &[0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01]);
@@ -72,11 +84,48 @@ fn write_float() {
     assert_eq!(&writer.get_ref()[0..8], &[0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D]);
 }
 
+#[test]
+fn write_slice() {
+    let mut out = Vec::new();
+    out.write_u16_slice::<BigEndian>(&[0x0123, 0x4567]).unwrap();
+    out.write_u16_slice::<LittleEndian>(&[0x0123, 0x4567]).unwrap();
+    out.write_i32_slice::<BigEndian>(&[-2]).unwrap();
+    out.write_u64_slice::<LittleEndian>(&[]).unwrap();
+    out.write_u8_slice(&[0x89, 0xab]).unwrap();
+    out.write_i8_slice(&[-1, 2, -128]).unwrap();
+    assert_eq!(out, [0x01, 0x23, 0x45, 0x67, 0x23, 0x01, 0x67, 0x45, 0xff, 0xff, 0xff, 0xfe,
+                     0x89, 0xab, 0xff, 0x02, 0x80]);
+
+    let mut out = Vec::new();
+    out.write_f32_slice::<LittleEndian>(&[10.12f32]).unwrap();
+    out.write_f64_slice::<BigEndian>(&[10.12f64]).unwrap();
+    assert_eq!(out, [0x85, 0xEB, 0x21, 0x41, 0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D]);
+}
+
+#[test]
+fn write_slice_roundtrip() {
+    // Large enough to span several conversion chunks
+    let vals: Vec<u32> = (0..1000).map(|v| v << 16).collect();
+
+    let mut out = Vec::new();
+    out.write_u32_slice::<BigEndian>(&vals).unwrap();
+    assert_eq!(out.len(), 4000);
+    assert_eq!(&out[4..8], &[0x00, 0x01, 0x00, 0x00]);
+
+    let mut back = vec![0u32; 1000];
+    (&out[..]).read_u32_into::<BigEndian>(&mut back).unwrap();
+    assert_eq!(back, vals);
+}
+
 #[test]
 fn read_be() {
-    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                       0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
     let mut reader = io::Cursor::new(buf);
 
+    reader.set_position(0);
+    assert_eq!(reader.read_u128::<BigEndian>().unwrap(), 0x0123456789abcdeffedcba9876543210);
+
     reader.set_position(0);
     assert_eq!(reader.read_u64::<BigEndian>().unwrap(), 0x0123456789abcdef);
 
@@ -89,9 +138,13 @@ fn read_be() {
 
 #[test]
 fn read_le() {
-    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                       0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
     let mut reader = io::Cursor::new(buf);
 
+    reader.set_position(0);
+    assert_eq!(reader.read_u128::<LittleEndian>().unwrap(), 0x1032547698badcfeefcdab8967452301);
+
     reader.set_position(0);
     assert_eq!(reader.read_u64::<LittleEndian>().unwrap(), 0xefcdab8967452301);
 
@@ -111,6 +164,17 @@ fn read_octet() {
     assert_eq!(reader.read_u8().unwrap(), 0x01);
 }
 
+#[test]
+fn read_signed() {
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe];
+    assert_eq!(buf.read_i128::<BigEndian>().unwrap(), -2);
+
+    let mut buf: &[u8] = &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    assert_eq!(buf.read_i128::<LittleEndian>().unwrap(), -2);
+}
+
 #[test]
 fn read_float() {
     let mut buf: &[u8] = &[0x41, 0x21, 0xEB, 0x85];
@@ -126,6 +190,112 @@ fn read_float() {
     assert_eq!(buf.read_f64::<LittleEndian>().unwrap(), 10.12f64);
 }
 
+#[test]
+fn native_network() {
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67];
+    let mut reader = io::Cursor::new(buf);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_u32::<NetworkEndian>().unwrap(), 0x01234567);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_u32::<NativeEndian>().unwrap(), u32::from_ne_bytes([0x01, 0x23, 0x45, 0x67]));
+
+    let buf: &mut [u8] = &mut [0u8; 4];
+    let mut writer = io::Cursor::new(buf);
+
+    writer.set_position(0);
+    writer.write_u32::<NativeEndian>(0x01_23_45_67).unwrap();
+    assert_eq!(&writer.get_ref()[0..4], &u32::to_ne_bytes(0x01_23_45_67));
+}
+
+#[test]
+fn write_dyn() {
+    let buf: &mut [u8] = &mut [0u8; 8];
+    let mut writer = io::Cursor::new(buf);
+
+    writer.set_position(0);
+    writer.write_u32_dyn(Endian::Big, 0x01_23_45_67).unwrap();
+    assert_eq!(&writer.get_ref()[0..4], &[0x01, 0x23, 0x45, 0x67]);
+
+    writer.set_position(0);
+    writer.write_u32_dyn(Endian::Little, 0x01_23_45_67).unwrap();
+    assert_eq!(&writer.get_ref()[0..4], &[0x67, 0x45, 0x23, 0x01]);
+
+    writer.set_position(0);
+    writer.write_f64_dyn(Endian::Big, 10.12f64).unwrap();
+    assert_eq!(&writer.get_ref()[0..8], &[0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D]);
+}
+
+#[test]
+fn read_dyn() {
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let mut reader = io::Cursor::new(buf);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_u64_dyn(Endian::Big).unwrap(), 0x0123456789abcdef);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_u64_dyn(Endian::Little).unwrap(), 0xefcdab8967452301);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_i16_dyn(Endian::Big).unwrap(), 0x0123);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_u16_dyn(Endian::Little).unwrap(), 0x2301);
+}
+
+#[test]
+fn read_into() {
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let mut reader = io::Cursor::new(buf);
+
+    let mut dst = [0u16; 4];
+    reader.set_position(0);
+    reader.read_u16_into::<BigEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [0x0123, 0x4567, 0x89ab, 0xcdef]);
+
+    reader.set_position(0);
+    reader.read_u16_into::<LittleEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [0x2301, 0x6745, 0xab89, 0xefcd]);
+
+    let mut dst = [0u32; 2];
+    reader.set_position(0);
+    reader.read_u32_into::<BigEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [0x01234567, 0x89abcdef]);
+
+    let mut dst = [0i64; 1];
+    reader.set_position(0);
+    reader.read_i64_into::<LittleEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [0xefcdab8967452301u64 as i64]);
+
+    let mut dst = [0u32; 3];
+    reader.set_position(0);
+    assert!(reader.read_u32_into::<BigEndian>(&mut dst).is_err());
+
+    let mut dst: [u64; 0] = [];
+    reader.set_position(8);
+    reader.read_u64_into::<BigEndian>(&mut dst).unwrap();
+}
+
+#[test]
+fn read_float_into() {
+    let mut buf: &[u8] = &[0x41, 0x21, 0xEB, 0x85, 0x85, 0xEB, 0x21, 0x41];
+    let mut dst = [0f32; 1];
+    buf.read_f32_into::<BigEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [10.12f32]);
+    buf.read_f32_into::<LittleEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [10.12f32]);
+
+    let mut buf: &[u8] = &[0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D,
+                           0x3D, 0x0A, 0xD7, 0xA3, 0x70, 0x3D, 0x24, 0x40];
+    let mut dst = [0f64; 1];
+    buf.read_f64_into::<BigEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [10.12f64]);
+    buf.read_f64_into::<LittleEndian>(&mut dst).unwrap();
+    assert_eq!(dst, [10.12f64]);
+}
+
 #[test]
 fn read_exact() {
     let mut buf: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
@@ -137,3 +307,47 @@ fn read_exact() {
     assert!(buf.read_exact(1).is_err());
     assert_eq!(buf.read_exact(0).unwrap(), []);
 }
+
+#[test]
+fn prefixed_bytes() {
+    let mut out = Vec::new();
+    out.write_prefixed_bytes::<u8, BigEndian>(b"ab").unwrap();
+    out.write_prefixed_bytes::<u16, LittleEndian>(b"cd").unwrap();
+    out.write_prefixed_bytes::<u32, BigEndian>(b"").unwrap();
+    out.write_prefixed_bytes::<podio::Uleb128, BigEndian>(b"e").unwrap();
+    assert_eq!(out, [2, b'a', b'b', 2, 0, b'c', b'd', 0, 0, 0, 0, 1, b'e']);
+
+    let mut reader = &out[..];
+    assert_eq!(reader.read_prefixed_bytes::<u8, BigEndian>(16).unwrap(), b"ab");
+    assert_eq!(reader.read_prefixed_bytes::<u16, LittleEndian>(16).unwrap(), b"cd");
+    assert_eq!(reader.read_prefixed_bytes::<u32, BigEndian>(16).unwrap(), b"");
+    assert_eq!(reader.read_prefixed_bytes::<podio::Uleb128, BigEndian>(16).unwrap(), b"e");
+    assert_eq!(reader.read_prefixed_bytes::<u8, BigEndian>(16).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn prefixed_bytes_limits() {
+    let mut out = Vec::new();
+    let long = [0u8; 256];
+    assert_eq!(out.write_prefixed_bytes::<u8, BigEndian>(&long).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+    out.write_prefixed_bytes::<u8, BigEndian>(&long[..255]).unwrap();
+    assert_eq!(out.len(), 256);
+
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+    assert_eq!(buf.read_prefixed_bytes::<u32, BigEndian>(1 << 20).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[3, 1, 2, 3];
+    assert_eq!(buf.read_prefixed_bytes::<u8, BigEndian>(3).unwrap(), [1, 2, 3]);
+    let mut buf: &[u8] = &[3, 1, 2, 3];
+    assert_eq!(buf.read_prefixed_bytes::<u8, BigEndian>(2).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_exact_limited() {
+    let mut buf: &[u8] = &[1, 2, 3, 4];
+    assert_eq!(buf.read_exact_limited(2, 2).unwrap(), [1, 2]);
+    assert_eq!(buf.read_exact_limited(2, 1).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(buf.read_exact_limited(2, 4).unwrap(), [3, 4]);
+    assert_eq!(buf.read_exact_limited(1, 4).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,44 @@
+extern crate podio;
+
+use podio::{BigEndian, LittleEndian, ReadPodExt, SliceReader};
+use podio::{decode_f64_be, decode_i16_le, decode_u128_be, decode_u16_le, decode_u32_be};
+use podio::{encode_f32_le, encode_i64_be, encode_u128_le, encode_u16_be, encode_u32_be, encode_u32_le};
+
+const HEADER: [u8; 4] = encode_u32_be(0x89504e47);
+const MAGIC: u32 = decode_u32_be(*b"\x89PNG");
+const TABLE: [[u8; 2]; 3] = [encode_u16_be(1), encode_u16_be(0x0100), encode_u16_be(0xfffe)];
+
+#[test]
+fn const_context() {
+    assert_eq!(HEADER, *b"\x89PNG");
+    assert_eq!(MAGIC, 0x89504e47);
+    assert_eq!(TABLE, [[0x00, 0x01], [0x01, 0x00], [0xff, 0xfe]]);
+}
+
+#[test]
+fn encode() {
+    assert_eq!(encode_u32_le(0x01234567), [0x67, 0x45, 0x23, 0x01]);
+    assert_eq!(encode_i64_be(-2), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+    assert_eq!(encode_f32_le(10.12), [0x85, 0xEB, 0x21, 0x41]);
+    assert_eq!(encode_u128_le(0x0123456789abcdeffedcba9876543210),
+               [0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe,
+                0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01]);
+}
+
+#[test]
+fn decode() {
+    assert_eq!(decode_u16_le([0x23, 0x01]), 0x0123);
+    assert_eq!(decode_i16_le([0xfe, 0xff]), -2);
+    assert_eq!(decode_f64_be([0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D]), 10.12);
+    assert_eq!(decode_u128_be([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                               0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10]),
+               0x0123456789abcdeffedcba9876543210);
+}
+
+#[test]
+fn matches_readers() {
+    let buf = encode_u32_le(0xdeadbeef);
+    let mut reader: &[u8] = &buf;
+    assert_eq!(reader.read_u32::<LittleEndian>().unwrap(), 0xdeadbeef);
+    assert_eq!(SliceReader::new(&buf).read_u32::<BigEndian>(), Ok(decode_u32_be(buf)));
+}
@@ -0,0 +1,40 @@
+extern crate podio;
+
+use podio::{BigEndian, ErrorKind, LittleEndian, UnexpectedEof};
+use podio::{ReadPodExt, WritePodExt};
+
+#[test]
+fn slice_reader() {
+    let mut reader: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab];
+    assert_eq!(reader.read_u32::<BigEndian>().unwrap(), 0x01_23_45_67);
+    assert_eq!(reader.read_u16::<LittleEndian>().unwrap(), 0xab_89);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn slice_reader_eof() {
+    let mut reader: &[u8] = &[0x01, 0x23];
+    let err = reader.read_u32::<BigEndian>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>());
+    assert_eq!(eof.map(|e| e.bytes_read()), Some(2));
+}
+
+#[test]
+fn vec_writer() {
+    let mut writer = Vec::new();
+    writer.write_u32::<BigEndian>(0x01_23_45_67).unwrap();
+    writer.write_u16::<LittleEndian>(0xab_89).unwrap();
+    writer.write_uleb128(300).unwrap();
+    assert_eq!(writer, [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xac, 0x02]);
+}
+
+#[test]
+fn slice_writer_full() {
+    let mut buf = [0u8; 3];
+    let mut writer: &mut [u8] = &mut buf;
+    let err = writer.write_u32::<BigEndian>(0x01_23_45_67).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(buf, [0x01, 0x23, 0x45]);
+}
@@ -1,7 +1,9 @@
+#![cfg(feature = "std")]
+
 extern crate podio;
 
 use std::io::{self, Read};
-use podio::ReadPodExt;
+use podio::{ReadPodExt, UnexpectedEof};
 
 struct TestReader {
     state: Option<io::Result<usize>>,
@@ -46,12 +48,52 @@ fn interrupted() {
 fn eof() {
     // Getting a Ok(0) implies an unexpected EOF
     let first_read = Ok(0);
-    assert!(TestReader::test(first_read).is_err());
+    let err = TestReader::test(first_read).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap();
+    assert!(eof.is_clean());
+    assert_eq!(eof.bytes_expected(), 4);
+}
+
+#[test]
+fn eof_partial() {
+    // The number of bytes read before the EOF is reported
+    let mut reader: &[u8] = &[0xA5, 0xA5, 0xA5];
+    let err = reader.read_u32::<podio::LittleEndian>().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap();
+    assert!(!eof.is_clean());
+    assert_eq!(eof.bytes_read(), 3);
+    assert_eq!(eof.bytes_expected(), 4);
 }
 
 #[test]
+#[allow(clippy::io_other_error)] // io::Error::other needs Rust 1.74
 fn err() {
     // Getting any other error is still an error
     let first_read = Err(io::Error::new(io::ErrorKind::Other, "Other"));
     assert!(TestReader::test(first_read).is_err());
 }
+
+#[test]
+fn read_exact_huge() {
+    // A bogus length must not be allocated before the data arrives
+    let mut reader: &[u8] = &[0xA5; 10];
+    let err = ReadPodExt::read_exact(&mut reader, usize::MAX >> 1).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap();
+    assert_eq!(eof.bytes_read(), 10);
+    assert_eq!(eof.bytes_expected(), usize::MAX >> 1);
+}
+
+#[test]
+fn read_exact_chunked() {
+    // Spans several buffer growths, one byte per read
+    let mut reader = TestReader::new(Ok(1));
+    let data = ReadPodExt::read_exact(&mut reader, 100_000).unwrap();
+    assert_eq!(data.len(), 100_000);
+    assert!(data.iter().all(|&b| b == 0xA5));
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "std")]
+
+extern crate podio;
+
+use std::io;
+use podio::{LittleEndian, BigEndian};
+use podio::{ReadPodExt, WritePodExt};
+
+#[test]
+fn read_pod() {
+    let buf: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let mut reader = io::Cursor::new(buf);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_pod::<u64, BigEndian>().unwrap(), 0x0123456789abcdef);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_pod::<u32, LittleEndian>().unwrap(), 0x67452301);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_pod::<i16, BigEndian>().unwrap(), 0x0123);
+
+    reader.set_position(0);
+    assert_eq!(reader.read_pod::<u8, BigEndian>().unwrap(), 0x01);
+
+    let mut buf: &[u8] = &[0x41, 0x21, 0xEB, 0x85];
+    assert_eq!(buf.read_pod::<f32, BigEndian>().unwrap(), 10.12f32);
+}
+
+#[test]
+fn write_pod() {
+    let mut out = Vec::new();
+    out.write_pod::<BigEndian>(&0x0123u16).unwrap();
+    out.write_pod::<LittleEndian>(&0x0123u16).unwrap();
+    out.write_pod::<LittleEndian>(&-1i8).unwrap();
+    out.write_pod::<BigEndian>(&10.12f32).unwrap();
+    assert_eq!(out, [0x01, 0x23, 0x23, 0x01, 0xff, 0x41, 0x21, 0xEB, 0x85]);
+}
+
+#[test]
+fn read_pod_array() {
+    let mut buf: &[u8] = &[0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x40];
+    assert_eq!(buf.read_pod::<[f32; 3], LittleEndian>().unwrap(), [1.0, 2.0, 3.0]);
+
+    let mut buf: &[u8] = &[0x00, 0x01, 0x00, 0x02, 0x00];
+    assert_eq!(buf.read_pod::<[u16; 2], BigEndian>().unwrap(), [1, 2]);
+    assert_eq!(buf.read_pod::<[u8; 0], BigEndian>().unwrap(), []);
+    assert_eq!(buf.read_pod::<[u16; 1], BigEndian>().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut buf: &[u8] = &[1, 2, 3, 4];
+    assert_eq!(buf.read_pod::<[[u8; 2]; 2], BigEndian>().unwrap(), [[1, 2], [3, 4]]);
+}
+
+#[test]
+fn read_pod_tuple() {
+    let mut buf: &[u8] = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03];
+    assert_eq!(buf.read_pod::<(u16, u16, u32), BigEndian>().unwrap(), (1, 2, 3));
+
+    let mut buf: &[u8] = &[0x01, 0x02, 0x00, 0xff];
+    assert_eq!(buf.read_pod::<(u8, (u16,), i8), LittleEndian>().unwrap(), (1, (2,), -1));
+
+    let mut buf: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    let twelve = buf.read_pod::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8), BigEndian>().unwrap();
+    assert_eq!(twelve, (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12));
+}
+
+#[test]
+fn write_pod_compound() {
+    let mut out = Vec::new();
+    out.write_pod::<LittleEndian>(&[1.0f32, 2.0, 3.0]).unwrap();
+    out.write_pod::<BigEndian>(&(1u16, 2u16, 3u32)).unwrap();
+    out.write_pod::<BigEndian>(&[(1u8, -1i8); 2]).unwrap();
+    assert_eq!(out, [0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x40,
+                     0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+                     0x01, 0xff, 0x01, 0xff]);
+}
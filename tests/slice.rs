@@ -0,0 +1,175 @@
+extern crate podio;
+
+use podio::{LittleEndian, BigEndian, NativeEndian, NetworkEndian};
+use podio::{ArrayWriter, CapacityExceeded, SliceWriter};
+use podio::{Error, ErrorKind, ReadPodExt, SliceReader, UnexpectedEof, WritePodExt};
+
+const BUF: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                     0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+
+#[test]
+fn read_be() {
+    assert_eq!(SliceReader::new(BUF).read_u128::<BigEndian>(), Ok(0x0123456789abcdeffedcba9876543210));
+    assert_eq!(SliceReader::new(BUF).read_u64::<BigEndian>(), Ok(0x0123456789abcdef));
+    assert_eq!(SliceReader::new(BUF).read_u32::<BigEndian>(), Ok(0x01234567));
+    assert_eq!(SliceReader::new(BUF).read_u16::<BigEndian>(), Ok(0x0123));
+}
+
+#[test]
+fn read_le() {
+    assert_eq!(SliceReader::new(BUF).read_u128::<LittleEndian>(), Ok(0x1032547698badcfeefcdab8967452301));
+    assert_eq!(SliceReader::new(BUF).read_u64::<LittleEndian>(), Ok(0xefcdab8967452301));
+    assert_eq!(SliceReader::new(BUF).read_u32::<LittleEndian>(), Ok(0x67452301));
+    assert_eq!(SliceReader::new(BUF).read_u16::<LittleEndian>(), Ok(0x2301));
+}
+
+#[test]
+fn read_octet() {
+    let mut reader = SliceReader::new(BUF);
+    assert_eq!(reader.read_u8(), Ok(0x01));
+    assert_eq!(reader.read_i8(), Ok(0x23));
+    assert_eq!(reader.position(), 2);
+}
+
+#[test]
+fn read_signed() {
+    let mut reader = SliceReader::new(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                                        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+    assert_eq!(reader.read_i128::<BigEndian>(), Ok(-2));
+
+    let mut reader = SliceReader::new(&[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                                        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(reader.read_i128::<LittleEndian>(), Ok(-2));
+}
+
+#[test]
+fn read_float() {
+    assert_eq!(SliceReader::new(&[0x41, 0x21, 0xEB, 0x85]).read_f32::<BigEndian>(), Ok(10.12f32));
+    assert_eq!(SliceReader::new(&[0x85, 0xEB, 0x21, 0x41]).read_f32::<LittleEndian>(), Ok(10.12f32));
+
+    let mut reader = SliceReader::new(&[0x40, 0x24, 0x3D, 0x70, 0xA3, 0xD7, 0x0A, 0x3D]);
+    assert_eq!(reader.read_f64::<BigEndian>(), Ok(10.12f64));
+
+    let mut reader = SliceReader::new(&[0x3D, 0x0A, 0xD7, 0xA3, 0x70, 0x3D, 0x24, 0x40]);
+    assert_eq!(reader.read_f64::<LittleEndian>(), Ok(10.12f64));
+}
+
+#[test]
+fn native_network() {
+    assert_eq!(SliceReader::new(BUF).read_u32::<NetworkEndian>(), Ok(0x01234567));
+    assert_eq!(SliceReader::new(BUF).read_u32::<NativeEndian>(),
+               Ok(u32::from_ne_bytes([0x01, 0x23, 0x45, 0x67])));
+}
+
+#[test]
+fn read_bytes() {
+    let mut reader = SliceReader::new(BUF);
+    reader.skip(2).unwrap();
+
+    let bytes = reader.read_bytes(4).unwrap();
+    assert_eq!(bytes, &BUF[2..6]);
+    assert_eq!(bytes.as_ptr(), BUF[2..].as_ptr());
+    assert_eq!(reader.remaining(), &BUF[6..]);
+    assert_eq!(reader.len(), 10);
+
+    assert_eq!(reader.read_bytes(10).unwrap(), &BUF[6..]);
+    assert!(reader.is_empty());
+    assert_eq!(reader.read_bytes(0), Ok(&[][..]));
+}
+
+#[test]
+fn out_of_bounds() {
+    let mut reader = SliceReader::new(&BUF[..6]);
+    reader.read_u16::<BigEndian>().unwrap();
+
+    let err = reader.read_u64::<BigEndian>().unwrap_err();
+    assert_eq!(err.offset(), 2);
+    assert_eq!(err.requested(), 8);
+    assert_eq!(err.available(), 4);
+
+    // A failed read does not consume anything
+    assert_eq!(reader.position(), 2);
+    assert_eq!(reader.read_u32::<BigEndian>(), Ok(0x456789ab));
+}
+
+#[test]
+fn into_error() {
+    let err: Error = SliceReader::new(&BUF[..3]).read_u32::<BigEndian>().unwrap_err().into();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let eof = err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap();
+    assert_eq!(eof.bytes_read(), 3);
+    assert_eq!(eof.bytes_expected(), 4);
+}
+
+#[test]
+fn read_pod_ext() {
+    let mut reader = SliceReader::new(&[0xac, 0x02, 0x01]);
+    assert_eq!(reader.read_uleb128().unwrap(), 300);
+    assert_eq!(reader.position(), 2);
+    assert_eq!(reader.read_u8(), Ok(0x01));
+}
+
+#[test]
+fn slice_writer() {
+    let mut buf = [0u8; 16];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert_eq!(writer.capacity(), 16);
+
+    writer.write_u64::<BigEndian>(0x0123456789abcdef).unwrap();
+    writer.write_u32::<LittleEndian>(0x98badcfe).unwrap();
+    writer.write_u16::<BigEndian>(0x7654).unwrap();
+    assert_eq!(writer.position(), 14);
+    assert_eq!(writer.remaining(), 2);
+    assert_eq!(writer.written(), &BUF[..14]);
+
+    writer.write_bytes(&[0x32, 0x10]).unwrap();
+    assert!(writer.is_full());
+    assert_eq!(writer.into_written(), BUF);
+}
+
+#[test]
+fn slice_writer_overflow() {
+    let mut buf = [0u8; 6];
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.write_u16::<BigEndian>(0x0123).unwrap();
+
+    let err = writer.write_u64::<BigEndian>(0).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    let full = err.get_ref().and_then(|e| e.downcast_ref::<CapacityExceeded>()).unwrap();
+    assert_eq!(full.offset(), 2);
+    assert_eq!(full.requested(), 8);
+    assert_eq!(full.available(), 4);
+
+    // A single write that does not fit writes nothing
+    assert_eq!(writer.position(), 2);
+    writer.write_u32::<BigEndian>(0x456789ab).unwrap();
+    assert_eq!(writer.write_bytes(&[0]).unwrap_err().offset(), 6);
+    assert_eq!(buf, BUF[..6]);
+}
+
+#[test]
+fn array_writer() {
+    let mut writer = ArrayWriter::<8>::new();
+    writer.write_u32::<BigEndian>(0x01234567).unwrap();
+    writer.write_i16::<BigEndian>(-2).unwrap();
+    assert_eq!(writer.written(), &[0x01, 0x23, 0x45, 0x67, 0xff, 0xfe]);
+    assert_eq!(writer.remaining(), 2);
+
+    let err = writer.write_u32::<BigEndian>(0).unwrap_err();
+    let full = err.get_ref().and_then(|e| e.downcast_ref::<CapacityExceeded>()).unwrap();
+    assert_eq!(full.available(), 2);
+
+    writer.write_u16::<BigEndian>(0).unwrap();
+    assert!(writer.is_full());
+    assert_eq!(writer.into_inner(), ([0x01, 0x23, 0x45, 0x67, 0xff, 0xfe, 0x00, 0x00], 8));
+}
+
+#[test]
+fn slice_writer_partial() {
+    // Writes made of several calls keep the parts that fit
+    let mut buf = [0u8; 4];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert!(writer.write_prefixed_bytes::<u16, BigEndian>(b"abc").is_err());
+    assert_eq!(writer.written(), &[0x00, 0x03]);
+}
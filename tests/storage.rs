@@ -0,0 +1,91 @@
+extern crate podio;
+
+use std::collections::BTreeSet;
+use std::mem;
+
+use podio::{BigEndian, LittleEndian, NativeEndian};
+use podio::{F32, F64, I16, I64, U128, U16, U32, U64};
+
+#[repr(C)]
+struct Header {
+    magic: U32<BigEndian>,
+    version: U16<LittleEndian>,
+    flags: U16<BigEndian>,
+    size: U64<LittleEndian>,
+}
+
+#[test]
+fn layout() {
+    assert_eq!(mem::size_of::<U16<BigEndian>>(), 2);
+    assert_eq!(mem::size_of::<U128<LittleEndian>>(), 16);
+    assert_eq!(mem::size_of::<F64<BigEndian>>(), 8);
+    assert_eq!(mem::align_of::<U64<LittleEndian>>(), 1);
+    assert_eq!(mem::size_of::<Header>(), 16);
+    assert_eq!(mem::align_of::<Header>(), 1);
+}
+
+#[test]
+fn overlay() {
+    let buf: [u8; 16] = [0x89, b'P', b'N', b'G', 0x02, 0x00, 0x80, 0x01,
+                         0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    // The header has an alignment of 1 and no padding, so any 16 bytes are a valid header
+    let header = unsafe { &*(buf.as_ptr() as *const Header) };
+
+    assert_eq!(header.magic.get(), 0x89504e47);
+    assert_eq!(header.version.get(), 2);
+    assert_eq!(header.flags.get(), 0x8001);
+    assert_eq!(header.size.get(), 16);
+}
+
+#[test]
+fn get_set() {
+    let mut val = U32::<BigEndian>::new(0x01234567);
+    assert_eq!(val.to_bytes(), [0x01, 0x23, 0x45, 0x67]);
+    assert_eq!(val.get(), 0x01234567);
+
+    val.set(0x89abcdef);
+    assert_eq!(val.to_bytes(), [0x89, 0xab, 0xcd, 0xef]);
+
+    let val = U32::<LittleEndian>::from_bytes([0x67, 0x45, 0x23, 0x01]);
+    assert_eq!(val.get(), 0x01234567);
+
+    let val = U32::<NativeEndian>::new(0x01234567);
+    assert_eq!(val.to_bytes(), 0x01234567u32.to_ne_bytes());
+
+    assert_eq!(I16::<LittleEndian>::new(-2).to_bytes(), [0xfe, 0xff]);
+    assert_eq!(F32::<BigEndian>::new(10.12).to_bytes(), [0x41, 0x21, 0xEB, 0x85]);
+    assert_eq!(F64::<LittleEndian>::default().get(), 0.0);
+}
+
+#[test]
+fn conversions() {
+    let val: U64<BigEndian> = 0x0123456789abcdef.into();
+    assert_eq!(val.to_bytes(), [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+    assert_eq!(u64::from(val), 0x0123456789abcdef);
+
+    let val = I64::<LittleEndian>::from(-1);
+    let raw: i64 = val.into();
+    assert_eq!(raw, -1);
+}
+
+#[test]
+fn logical_comparisons() {
+    // The little endian bytes of 256 sort before those of 1, the values do not
+    let one = U16::<LittleEndian>::new(1);
+    let big = U16::<LittleEndian>::new(256);
+    assert!(one < big);
+    assert_ne!(one, big);
+    assert_eq!(one, U16::from_bytes([0x01, 0x00]));
+
+    let set: BTreeSet<_> = [3, 1, 2].iter().map(|&v| U32::<LittleEndian>::new(v)).collect();
+    assert_eq!(set.iter().map(|v| v.get()).collect::<Vec<_>>(), [1, 2, 3]);
+
+    assert!(F32::<BigEndian>::new(-1.5) < F32::new(0.5));
+    assert_ne!(F64::<BigEndian>::new(f64::NAN), F64::new(f64::NAN));
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", U16::<BigEndian>::new(0x0123)), "U16(291)");
+    assert_eq!(format!("{:?}", F64::<LittleEndian>::new(1.5)), "F64(1.5)");
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "std")]
+
+extern crate podio;
+
+use std::ffi::CString;
+use std::io;
+use podio::{ReadPodExt, WritePodExt, LittleEndian, BigEndian, LimitExceeded, UnexpectedEof};
+
+#[test]
+fn read_cstring() {
+    let mut buf: &[u8] = b".text\0.data\0\0rest";
+    assert_eq!(buf.read_cstring(16).unwrap().as_bytes(), b".text");
+    assert_eq!(buf.read_cstring(5).unwrap().as_bytes(), b".data");
+    assert_eq!(buf.read_cstring(0).unwrap().as_bytes(), b"");
+    assert_eq!(buf, b"rest");
+}
+
+#[test]
+fn read_cstring_errors() {
+    let mut buf: &[u8] = b"toolong\0";
+    let err = buf.read_cstring(6).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let limit = err.get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()).unwrap();
+    assert_eq!(limit.requested(), 7);
+    assert_eq!(limit.limit(), 6);
+
+    let mut buf: &[u8] = b"unterminated";
+    assert_eq!(buf.read_cstring(64).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut buf: &[u8] = b"";
+    assert_eq!(buf.read_cstring(64).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+fn eof(err: &io::Error) -> UnexpectedEof {
+    *err.get_ref().and_then(|e| e.downcast_ref::<UnexpectedEof>()).unwrap()
+}
+
+#[test]
+fn read_cstring_eof() {
+    // A clean end of stream is told apart from a truncated name
+    let mut buf: &[u8] = b"";
+    let err = buf.read_cstring(64).unwrap_err();
+    assert!(eof(&err).is_clean());
+
+    let mut buf: &[u8] = b"ab";
+    let err = buf.read_cstring(64).unwrap_err();
+    assert!(!eof(&err).is_clean());
+    assert_eq!(eof(&err).bytes_read(), 2);
+    assert_eq!(eof(&err).bytes_expected(), 3);
+}
+
+#[test]
+fn write_cstring() {
+    let mut out = Vec::new();
+    out.write_cstring(&CString::new("ELF").unwrap()).unwrap();
+    out.write_cstring(&CString::new("").unwrap()).unwrap();
+    assert_eq!(out, b"ELF\0\0");
+}
+
+#[test]
+fn read_fixed_str() {
+    let mut buf: &[u8] = b"ustar\0CD001   \0\0\0\0";
+    assert_eq!(buf.read_fixed_str(6, 0).unwrap(), "ustar");
+    assert_eq!(buf.read_fixed_str(8, b' ').unwrap(), "CD001");
+    assert_eq!(buf.read_fixed_str(4, 0).unwrap(), "");
+    assert_eq!(buf.read_fixed_str(1, 0).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut buf: &[u8] = b"a b  ";
+    assert_eq!(buf.read_fixed_str(5, b' ').unwrap(), "a b");
+
+    let mut buf: &[u8] = &[0xff, 0x00];
+    assert_eq!(buf.read_fixed_str(2, 0).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn write_fixed_str() {
+    let mut out = Vec::new();
+    out.write_fixed_str("ustar", 6, 0).unwrap();
+    out.write_fixed_str("CD001", 8, b' ').unwrap();
+    out.write_fixed_str("full", 4, 0).unwrap();
+    assert_eq!(out, b"ustar\0CD001   full");
+
+    assert_eq!(out.write_fixed_str("toolong", 6, 0).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(out.len(), 18);
+}
+
+#[test]
+fn read_utf16() {
+    let mut buf: &[u8] = &[0x50, 0x00, 0x45, 0x00, 0x3d, 0xd8, 0x00, 0xde];
+    assert_eq!(buf.read_utf16::<LittleEndian>(4).unwrap(), "PE\u{1F600}");
+
+    let mut buf: &[u8] = &[0x00, 0x50, 0x00, 0x45, 0xd8, 0x3d, 0xde, 0x00];
+    assert_eq!(buf.read_utf16::<BigEndian>(4).unwrap(), "PE\u{1F600}");
+
+    let mut buf: &[u8] = &[0x00, 0x50];
+    assert_eq!(buf.read_utf16::<BigEndian>(2).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_utf16_surrogates() {
+    // Lone high surrogate followed by 'A'
+    let data: &[u8] = &[0x3d, 0xd8, 0x41, 0x00];
+
+    let mut buf = data;
+    assert_eq!(buf.read_utf16::<LittleEndian>(2).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf = data;
+    assert_eq!(buf.read_utf16_lossy::<LittleEndian>(2).unwrap(), "\u{FFFD}A");
+}
+
+#[test]
+fn read_utf16_nul() {
+    let mut buf: &[u8] = &[0x00, 0x4c, 0x00, 0x4e, 0x00, 0x4b, 0x00, 0x00, 0xdc, 0x00, 0x00, 0x00];
+    assert_eq!(buf.read_utf16_nul::<BigEndian>(3).unwrap(), "LNK");
+    assert_eq!(buf.read_utf16_nul_lossy::<BigEndian>(3).unwrap(), "\u{FFFD}");
+
+    let mut buf: &[u8] = &[0x41, 0x00, 0x42, 0x00, 0x00, 0x00];
+    assert_eq!(buf.read_utf16_nul::<LittleEndian>(1).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0x41, 0x00, 0x42];
+    assert_eq!(buf.read_utf16_nul::<LittleEndian>(8).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn write_utf16() {
+    let mut out = Vec::new();
+    out.write_utf16::<LittleEndian>("A\u{1F600}").unwrap();
+    out.write_utf16_nul::<BigEndian>("B").unwrap();
+    assert_eq!(out, [0x41, 0x00, 0x3d, 0xd8, 0x00, 0xde, 0x00, 0x42, 0x00, 0x00]);
+}
+
+#[test]
+fn write_utf16_nul_interior() {
+    let mut out = Vec::new();
+    let err = out.write_utf16_nul::<LittleEndian>("a\0b").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+
+    // Without a terminator the NUL is just another code unit
+    out.write_utf16::<LittleEndian>("a\0b").unwrap();
+    assert_eq!(out, [0x61, 0x00, 0x00, 0x00, 0x62, 0x00]);
+}
+
+#[test]
+fn read_utf16_nul_eof() {
+    let mut buf: &[u8] = &[];
+    assert!(eof(&buf.read_utf16_nul::<LittleEndian>(8).unwrap_err()).is_clean());
+
+    // Half a code unit after a complete one
+    let mut buf: &[u8] = &[0x41, 0x00, 0x42];
+    let err = buf.read_utf16_nul::<LittleEndian>(8).unwrap_err();
+    assert_eq!(eof(&err).bytes_read(), 3);
+    assert_eq!(eof(&err).bytes_expected(), 4);
+
+    let mut buf: &[u8] = &[0x41];
+    let err = buf.read_utf16_nul::<LittleEndian>(8).unwrap_err();
+    assert_eq!(eof(&err).bytes_read(), 1);
+    assert_eq!(eof(&err).bytes_expected(), 2);
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "std")]
+
+extern crate podio;
+
+use std::io;
+use podio::{ReadPodExt, WritePodExt};
+
+fn uleb(val: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.write_uleb128(val).unwrap();
+    out
+}
+
+fn sleb(val: i64) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.write_sleb128(val).unwrap();
+    out
+}
+
+#[test]
+fn write_uleb128() {
+    assert_eq!(uleb(0), [0x00]);
+    assert_eq!(uleb(127), [0x7f]);
+    assert_eq!(uleb(128), [0x80, 0x01]);
+    assert_eq!(uleb(624485), [0xE5, 0x8E, 0x26]);
+    assert_eq!(uleb(u64::MAX), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+}
+
+#[test]
+fn write_sleb128() {
+    assert_eq!(sleb(0), [0x00]);
+    assert_eq!(sleb(63), [0x3f]);
+    assert_eq!(sleb(64), [0xc0, 0x00]);
+    assert_eq!(sleb(-1), [0x7f]);
+    assert_eq!(sleb(-64), [0x40]);
+    assert_eq!(sleb(-65), [0xbf, 0x7f]);
+    assert_eq!(sleb(-123456), [0xC0, 0xBB, 0x78]);
+    assert_eq!(sleb(i64::MIN), [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]);
+    assert_eq!(sleb(i64::MAX), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+}
+
+#[test]
+fn roundtrip() {
+    for &val in &[0, 1, 127, 128, 300, 1 << 35, u64::MAX >> 1, u64::MAX] {
+        assert_eq!((&uleb(val)[..]).read_uleb128_strict().unwrap(), val);
+    }
+    for &val in &[0, 1, -1, 63, 64, -64, -65, 1 << 40, -(1 << 40), i64::MIN, i64::MAX] {
+        assert_eq!((&sleb(val)[..]).read_sleb128_strict().unwrap(), val);
+    }
+}
+
+#[test]
+fn overflow() {
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+    assert_eq!(buf.read_uleb128().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00];
+    assert_eq!(buf.read_uleb128().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert_eq!(buf.read_sleb128().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7e];
+    assert_eq!(buf.read_sleb128().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn overlong() {
+    let mut buf: &[u8] = &[0x80, 0x00];
+    assert_eq!(buf.read_uleb128().unwrap(), 0);
+    let mut buf: &[u8] = &[0x80, 0x00];
+    assert_eq!(buf.read_uleb128_strict().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0xff, 0x7f];
+    assert_eq!(buf.read_sleb128().unwrap(), -1);
+    let mut buf: &[u8] = &[0xff, 0x7f];
+    assert_eq!(buf.read_sleb128_strict().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf: &[u8] = &[0x80, 0x00];
+    assert_eq!(buf.read_sleb128_strict().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn truncated() {
+    let mut buf: &[u8] = &[0x80, 0x80];
+    assert_eq!(buf.read_uleb128().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut buf: &[u8] = &[0xc0];
+    assert_eq!(buf.read_sleb128().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn zigzag() {
+    let mut out = Vec::new();
+    out.write_zigzag_i32(0).unwrap();
+    out.write_zigzag_i32(-1).unwrap();
+    out.write_zigzag_i32(1).unwrap();
+    out.write_zigzag_i32(-2).unwrap();
+    out.write_zigzag_i32(i32::MAX).unwrap();
+    out.write_zigzag_i32(i32::MIN).unwrap();
+    assert_eq!(out, [0x00, 0x01, 0x02, 0x03, 0xfe, 0xff, 0xff, 0xff, 0x0f, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+
+    let mut reader = &out[..];
+    assert_eq!(reader.read_zigzag_i32().unwrap(), 0);
+    assert_eq!(reader.read_zigzag_i32().unwrap(), -1);
+    assert_eq!(reader.read_zigzag_i32().unwrap(), 1);
+    assert_eq!(reader.read_zigzag_i32().unwrap(), -2);
+    assert_eq!(reader.read_zigzag_i32().unwrap(), i32::MAX);
+    assert_eq!(reader.read_zigzag_i32().unwrap(), i32::MIN);
+    assert_eq!(reader.read_zigzag_i32().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn zigzag_i64() {
+    for &val in &[0, -1, 1, 1 << 40, -(1 << 40), i64::MIN, i64::MAX] {
+        let mut out = Vec::new();
+        out.write_zigzag_i64(val).unwrap();
+        assert_eq!((&out[..]).read_zigzag_i64().unwrap(), val);
+    }
+
+    // Does not fit in an i32
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0x1f];
+    assert_eq!(buf.read_zigzag_i32().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0x1f];
+    assert_eq!(buf.read_zigzag_i64().unwrap(), -(1 << 32));
+
+    // Truncated halfway
+    let mut buf: &[u8] = &[0x80];
+    assert_eq!(buf.read_zigzag_i64().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn quic_varint() {
+    // Examples from RFC 9000, appendix A.1
+    let mut buf: &[u8] = &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c];
+    assert_eq!(buf.read_quic_varint().unwrap(), 151288809941952652);
+    let mut buf: &[u8] = &[0x9d, 0x7f, 0x3e, 0x7d];
+    assert_eq!(buf.read_quic_varint().unwrap(), 494878333);
+    let mut buf: &[u8] = &[0x7b, 0xbd];
+    assert_eq!(buf.read_quic_varint().unwrap(), 15293);
+    let mut buf: &[u8] = &[0x25];
+    assert_eq!(buf.read_quic_varint().unwrap(), 37);
+
+    let mut out = Vec::new();
+    out.write_quic_varint(151288809941952652).unwrap();
+    out.write_quic_varint(494878333).unwrap();
+    out.write_quic_varint(15293).unwrap();
+    out.write_quic_varint(37).unwrap();
+    assert_eq!(out, [0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c, 0x9d, 0x7f, 0x3e, 0x7d, 0x7b, 0xbd, 0x25]);
+}
+
+#[test]
+fn quic_varint_limits() {
+    let mut out = Vec::new();
+    out.write_quic_varint((1 << 62) - 1).unwrap();
+    assert_eq!(out, [0xff; 8]);
+    assert_eq!(out.write_quic_varint(1 << 62).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(out.len(), 8);
+
+    // 37 encoded in two bytes
+    let mut buf: &[u8] = &[0x40, 0x25];
+    assert_eq!(buf.read_quic_varint().unwrap(), 37);
+    let mut buf: &[u8] = &[0x40, 0x25];
+    assert_eq!(buf.read_quic_varint_strict().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    let mut buf: &[u8] = &[0x7b, 0xbd];
+    assert_eq!(buf.read_quic_varint_strict().unwrap(), 15293);
+
+    let mut buf: &[u8] = &[0x9d, 0x7f];
+    assert_eq!(buf.read_quic_varint().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}